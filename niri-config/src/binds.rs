@@ -119,6 +119,8 @@ pub enum Action {
     CancelScreenshot,
     #[knuffel(skip)]
     ScreenshotTogglePointer,
+    #[knuffel(skip)]
+    ScreenshotSelectFocusedWindow,
     Screenshot(
         #[knuffel(property(name = "show-pointer"), default = true)] bool,
         // Path; not settable from knuffel
@@ -204,6 +206,8 @@ pub enum Action {
     SwapWindowLeft,
     SwapWindowRight,
     ToggleColumnTabbedDisplay,
+    TabifyRow,
+    UntabifyRow,
     SetColumnDisplay(#[knuffel(argument, str)] ColumnDisplay),
     CenterColumn,
     CenterWindow,
@@ -321,7 +325,13 @@ pub enum Action {
     SetColumnWidth(#[knuffel(argument, str)] SizeChange),
     ExpandColumnToAvailableWidth,
     SwitchLayout(#[knuffel(argument, str)] LayoutSwitchTarget),
+    #[knuffel(skip)]
+    SwitchWindowLayout {
+        window_id: u64,
+        layout: LayoutSwitchTarget,
+    },
     ShowHotkeyOverlay,
+    ShowWindowLabels,
     MoveWorkspaceToMonitorLeft,
     MoveWorkspaceToMonitorRight,
     MoveWorkspaceToMonitorDown,
@@ -357,6 +367,8 @@ pub enum Action {
     ToggleOverview,
     OpenOverview,
     CloseOverview,
+    ToggleZoomLens,
+    ToggleSpotlight,
     #[knuffel(skip)]
     ToggleWindowUrgent(u64),
     #[knuffel(skip)]
@@ -385,6 +397,8 @@ pub enum Action {
     MruSetScope(MruScope),
     #[knuffel(skip)]
     MruCycleScope,
+    #[knuffel(skip)]
+    MruStartSearch,
 }
 
 impl From<niri_ipc::Action> for Action {
@@ -491,6 +505,8 @@ impl From<niri_ipc::Action> for Action {
             niri_ipc::Action::SwapWindowRight {} => Self::SwapWindowRight,
             niri_ipc::Action::SwapWindowLeft {} => Self::SwapWindowLeft,
             niri_ipc::Action::ToggleColumnTabbedDisplay {} => Self::ToggleColumnTabbedDisplay,
+            niri_ipc::Action::TabifyRow {} => Self::TabifyRow,
+            niri_ipc::Action::UntabifyRow {} => Self::UntabifyRow,
             niri_ipc::Action::SetColumnDisplay { display } => Self::SetColumnDisplay(display),
             niri_ipc::Action::CenterColumn {} => Self::CenterColumn,
             niri_ipc::Action::CenterWindow { id: None } => Self::CenterWindow,
@@ -619,7 +635,12 @@ impl From<niri_ipc::Action> for Action {
             niri_ipc::Action::SetColumnWidth { change } => Self::SetColumnWidth(change),
             niri_ipc::Action::ExpandColumnToAvailableWidth {} => Self::ExpandColumnToAvailableWidth,
             niri_ipc::Action::SwitchLayout { layout } => Self::SwitchLayout(layout),
+            niri_ipc::Action::SwitchWindowLayout { id, layout } => Self::SwitchWindowLayout {
+                window_id: id,
+                layout,
+            },
             niri_ipc::Action::ShowHotkeyOverlay {} => Self::ShowHotkeyOverlay,
+            niri_ipc::Action::ShowWindowLabels {} => Self::ShowWindowLabels,
             niri_ipc::Action::MoveWorkspaceToMonitorLeft {} => Self::MoveWorkspaceToMonitorLeft,
             niri_ipc::Action::MoveWorkspaceToMonitorRight {} => Self::MoveWorkspaceToMonitorRight,
             niri_ipc::Action::MoveWorkspaceToMonitorDown {} => Self::MoveWorkspaceToMonitorDown,
@@ -688,6 +709,8 @@ impl From<niri_ipc::Action> for Action {
             niri_ipc::Action::ToggleOverview {} => Self::ToggleOverview,
             niri_ipc::Action::OpenOverview {} => Self::OpenOverview,
             niri_ipc::Action::CloseOverview {} => Self::CloseOverview,
+            niri_ipc::Action::ToggleZoomLens {} => Self::ToggleZoomLens,
+            niri_ipc::Action::ToggleSpotlight {} => Self::ToggleSpotlight,
             niri_ipc::Action::ToggleWindowUrgent { id } => Self::ToggleWindowUrgent(id),
             niri_ipc::Action::SetWindowUrgent { id } => Self::SetWindowUrgent(id),
             niri_ipc::Action::UnsetWindowUrgent { id } => Self::UnsetWindowUrgent(id),
@@ -77,6 +77,7 @@ pub struct Config {
     pub clipboard: Clipboard,
     pub hotkey_overlay: HotkeyOverlay,
     pub config_notification: ConfigNotification,
+    pub lock_screen: LockScreen,
     pub animations: Animations,
     pub gestures: Gestures,
     pub overview: Overview,
@@ -89,6 +90,7 @@ pub struct Config {
     pub debug: Debug,
     pub workspaces: Vec<Workspace>,
     pub recent_windows: RecentWindows,
+    pub zoom_lens: ZoomLens,
 }
 
 #[derive(Debug, Clone)]
@@ -193,9 +195,11 @@ where
                 "clipboard" => m_merge!(clipboard),
                 "hotkey-overlay" => m_merge!(hotkey_overlay),
                 "config-notification" => m_merge!(config_notification),
+                "lock-screen" => m_merge!(lock_screen),
                 "animations" => m_merge!(animations),
                 "gestures" => m_merge!(gestures),
                 "overview" => m_merge!(overview),
+                "zoom-lens" => m_merge!(zoom_lens),
                 "xwayland-satellite" => m_merge!(xwayland_satellite),
                 "switch-events" => m_merge!(switch_events),
                 "debug" => m_merge!(debug),
@@ -1426,6 +1430,9 @@ mod tests {
             config_notification: ConfigNotification {
                 disable_failed: false,
             },
+            lock_screen: LockScreen {
+                show_summary: false,
+            },
             animations: Animations {
                 off: false,
                 slowdown: 2.0,
@@ -2181,6 +2188,7 @@ mod tests {
                 on: false,
                 debounce_ms: 750,
                 open_delay_ms: 150,
+                persist_order: false,
                 highlight: MruHighlight {
                     active_color: Color {
                         r: 0.0,
@@ -2272,6 +2280,11 @@ mod tests {
                     },
                 ],
             },
+            zoom_lens: ZoomLens {
+                width: 400.0,
+                height: 300.0,
+                magnification: 2.0,
+            },
         }
         "#);
     }
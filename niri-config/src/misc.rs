@@ -101,6 +101,23 @@ impl MergeWith<ConfigNotificationPart> for ConfigNotification {
     }
 }
 
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LockScreen {
+    pub show_summary: bool,
+}
+
+#[derive(knuffel::Decode, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LockScreenPart {
+    #[knuffel(child)]
+    pub show_summary: Option<Flag>,
+}
+
+impl MergeWith<LockScreenPart> for LockScreen {
+    fn merge_with(&mut self, part: &LockScreenPart) {
+        merge!((self, part), show_summary);
+    }
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
 pub struct Clipboard {
     pub disable_primary: bool,
@@ -152,6 +169,39 @@ impl MergeWith<OverviewPart> for Overview {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ZoomLens {
+    pub width: f64,
+    pub height: f64,
+    pub magnification: f64,
+}
+
+impl Default for ZoomLens {
+    fn default() -> Self {
+        Self {
+            width: 400.,
+            height: 300.,
+            magnification: 2.,
+        }
+    }
+}
+
+#[derive(knuffel::Decode, Debug, Clone, Copy, PartialEq)]
+pub struct ZoomLensPart {
+    #[knuffel(child, unwrap(argument))]
+    pub width: Option<FloatOrInt<1, 65535>>,
+    #[knuffel(child, unwrap(argument))]
+    pub height: Option<FloatOrInt<1, 65535>>,
+    #[knuffel(child, unwrap(argument))]
+    pub magnification: Option<FloatOrInt<1, 16>>,
+}
+
+impl MergeWith<ZoomLensPart> for ZoomLens {
+    fn merge_with(&mut self, part: &ZoomLensPart) {
+        merge!((self, part), width, height, magnification);
+    }
+}
+
 #[derive(knuffel::Decode, Debug, Default, Clone, PartialEq, Eq)]
 pub struct Environment(#[knuffel(children)] pub Vec<EnvironmentVariable>);
 
@@ -3,7 +3,7 @@ use std::collections::HashSet;
 use knuffel::errors::DecodeError;
 use smithay::input::keyboard::Keysym;
 
-use crate::utils::{expect_only_children, MergeWith};
+use crate::utils::{expect_only_children, Flag, MergeWith};
 use crate::{Action, Bind, Color, FloatOrInt, Key, Modifiers, Trigger};
 
 #[derive(Debug, PartialEq)]
@@ -11,6 +11,7 @@ pub struct RecentWindows {
     pub on: bool,
     pub debounce_ms: u16,
     pub open_delay_ms: u16,
+    pub persist_order: bool,
     pub highlight: MruHighlight,
     pub previews: MruPreviews,
     pub binds: Vec<Bind>,
@@ -22,6 +23,7 @@ impl Default for RecentWindows {
             on: true,
             debounce_ms: 750,
             open_delay_ms: 150,
+            persist_order: false,
             highlight: MruHighlight::default(),
             previews: MruPreviews::default(),
             binds: default_binds(),
@@ -40,6 +42,8 @@ pub struct RecentWindowsPart {
     #[knuffel(child, unwrap(argument))]
     pub open_delay_ms: Option<u16>,
     #[knuffel(child)]
+    pub persist_order: Option<Flag>,
+    #[knuffel(child)]
     pub highlight: Option<MruHighlightPart>,
     #[knuffel(child)]
     pub previews: Option<MruPreviewsPart>,
@@ -55,7 +59,7 @@ impl MergeWith<RecentWindowsPart> for RecentWindows {
         }
 
         merge_clone!((self, part), debounce_ms, open_delay_ms);
-        merge!((self, part), highlight, previews);
+        merge!((self, part), persist_order, highlight, previews);
 
         if let Some(part) = &part.binds {
             // Remove existing binds matching any new bind.
@@ -77,6 +77,14 @@ pub enum Request {
     Layers,
     /// Request information about the configured keyboard layouts.
     KeyboardLayouts,
+    /// Request the keyboard layout of a window.
+    ///
+    /// With `track-layout "window"`, every window has its own layout. Otherwise, this returns the
+    /// global layout.
+    WindowKeyboardLayout {
+        /// Id of the window.
+        id: u64,
+    },
     /// Request information about the focused output.
     FocusedOutput,
     /// Request information about the focused window.
@@ -87,6 +95,23 @@ pub enum Request {
     PickColor,
     /// Perform an action.
     Action(Action),
+    /// Perform an action written as command-line arguments.
+    ///
+    /// The arguments are the same as for `niri msg action`, for example `["focus-workspace",
+    /// "2"]`. This lets tools run actions by name without knowing their JSON form.
+    ActionFromArgs(Vec<String>),
+    /// Request the list of available actions.
+    ///
+    /// The compositor replies with [`Response::Actions`].
+    Actions,
+    /// Perform several actions in order, all at once.
+    ///
+    /// The actions run back-to-back within a single compositor event loop iteration, so
+    /// intermediate states never make it to the screen. An action that fails validation is
+    /// skipped without affecting the others.
+    ///
+    /// The compositor replies with [`Response::Batch`].
+    Batch(Vec<Action>),
     /// Change output configuration temporarily.
     ///
     /// The configuration is changed temporarily and not saved into the config file. If the output
@@ -149,6 +174,8 @@ pub enum Response {
     Layers(Vec<LayerSurface>),
     /// Information about the keyboard layout.
     KeyboardLayouts(KeyboardLayouts),
+    /// Keyboard layout of a window.
+    WindowKeyboardLayout(WindowKeyboardLayout),
     /// Information about the focused output.
     FocusedOutput(Option<Output>),
     /// Information about the focused window.
@@ -161,6 +188,10 @@ pub enum Response {
     OutputConfigChanged(OutputConfigChanged),
     /// Information about the overview.
     OverviewState(Overview),
+    /// Batch result, with one entry per requested action, in order.
+    Batch(Vec<Result<(), String>>),
+    /// Available actions.
+    Actions(Vec<ActionInfo>),
 }
 
 /// Overview information.
@@ -437,6 +468,10 @@ pub enum Action {
     SwapWindowLeft {},
     /// Toggle the focused column between normal and tabbed display.
     ToggleColumnTabbedDisplay {},
+    /// Move every tiled window on the focused workspace into one tabbed column.
+    TabifyRow {},
+    /// Spread the tiled windows on the focused workspace out into one column each.
+    UntabifyRow {},
     /// Set the display mode of the focused column.
     SetColumnDisplay {
         /// Display mode to set.
@@ -760,8 +795,22 @@ pub enum Action {
         #[cfg_attr(feature = "clap", arg())]
         layout: LayoutSwitchTarget,
     },
+    /// Switch the keyboard layout of a window.
+    ///
+    /// With `track-layout "window"`, the window gets this layout the next time it is focused, or
+    /// right away if it is focused. Otherwise, this switches the global layout.
+    SwitchWindowLayout {
+        /// Id of the window.
+        #[cfg_attr(feature = "clap", arg(long))]
+        id: u64,
+        /// Layout to switch to.
+        #[cfg_attr(feature = "clap", arg())]
+        layout: LayoutSwitchTarget,
+    },
     /// Show the hotkey overlay.
     ShowHotkeyOverlay {},
+    /// Label the visible windows and focus the one whose label is typed next.
+    ShowWindowLabels {},
     /// Move the focused workspace to the monitor to the left.
     MoveWorkspaceToMonitorLeft {},
     /// Move the focused workspace to the monitor to the right.
@@ -893,6 +942,10 @@ pub enum Action {
     OpenOverview {},
     /// Close the Overview.
     CloseOverview {},
+    /// Toggle (show/hide) the zoom lens magnifying the area under the cursor.
+    ToggleZoomLens {},
+    /// Toggle the spotlight dimming everything except the focused window.
+    ToggleSpotlight {},
     /// Toggle urgent status of a window.
     ToggleWindowUrgent {
         /// Id of the window to toggle urgent.
@@ -1426,6 +1479,30 @@ pub struct KeyboardLayouts {
     pub current_idx: u8,
 }
 
+/// An action that can be performed.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct ActionInfo {
+    /// Name of the action, as written for `niri msg action`.
+    pub name: String,
+    /// Short description of the action.
+    pub description: Option<String>,
+    /// Arguments of the action, as written for `niri msg action`.
+    ///
+    /// Flags start with `--`; other arguments are positional.
+    pub args: Vec<String>,
+}
+
+/// Keyboard layout of a window.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+pub struct WindowKeyboardLayout {
+    /// Index of the layout in [`KeyboardLayouts::names`].
+    pub idx: u8,
+    /// XKB name of the layout.
+    pub name: String,
+}
+
 /// A layer-shell layer.
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 #[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
@@ -180,6 +180,11 @@ impl Niri {
             nodes.push((ID_ANNOUNCEMENT, node));
         }
 
+        if update_focus && focus == ID_EXIT_CONFIRM_DIALOG {
+            // The description lists what will be closed, which changes every time the dialog opens.
+            nodes.push((ID_EXIT_CONFIRM_DIALOG, self.exit_confirm_dialog.a11y_node()));
+        }
+
         if focus == ID_MRU {
             // Ideally MRU would be a Group with a child Button for a window, but I've no idea how
             // to make it work reliably. When I did it that way, there were two issues:
@@ -301,7 +306,7 @@ impl Niri {
         let mut screenshot_ui = Node::new(Role::Group);
         screenshot_ui.set_label("Screenshot UI");
 
-        let exit_confirm_dialog = crate::ui::exit_confirm_dialog::a11y_node();
+        let exit_confirm_dialog = self.exit_confirm_dialog.a11y_node();
 
         let mut overview = Node::new(Role::Group);
         overview.set_label("Overview");
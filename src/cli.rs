@@ -1,9 +1,9 @@
 use std::ffi::OsString;
 use std::path::PathBuf;
 
-use clap::{Parser, Subcommand};
+use clap::{Command, Parser, Subcommand};
 use clap_complete::Shell;
-use niri_ipc::{Action, OutputAction};
+use niri_ipc::{Action, ActionInfo, OutputAction};
 
 use crate::utils::version;
 
@@ -71,6 +71,14 @@ pub enum Msg {
     Layers,
     /// Get the configured keyboard layouts.
     KeyboardLayouts,
+    /// Get the keyboard layout of a window.
+    WindowKeyboardLayout {
+        /// Id of the window.
+        ///
+        /// Run `niri msg windows` to see the window ids.
+        #[arg(long)]
+        id: u64,
+    },
     /// Print information about the focused output.
     FocusedOutput,
     /// Print information about the focused window.
@@ -84,6 +92,16 @@ pub enum Msg {
         #[command(subcommand)]
         action: Action,
     },
+    /// List the actions that can be performed.
+    Actions,
+    /// Perform several actions at once.
+    ///
+    /// Reads actions from stdin, one per line, written the same way as for `niri msg action`, for
+    /// example `focus-workspace 2`. Arguments are split on whitespace. Empty lines and lines
+    /// starting with `#` are ignored.
+    ///
+    /// The actions are performed together, so only the final result shows up on screen.
+    Batch,
     /// Change output configuration temporarily.
     ///
     /// The configuration is changed temporarily and not saved into the config file. If the output
@@ -109,6 +127,35 @@ pub enum Msg {
     OverviewState,
 }
 
+/// An action written as command-line arguments, like one line of `niri msg batch` input.
+#[derive(Parser)]
+#[command(no_binary_name = true)]
+pub struct BatchLine {
+    #[command(subcommand)]
+    pub action: Action,
+}
+
+/// Lists the actions accepted by `niri msg action`.
+///
+/// This comes from the same definitions as the CLI parser, so it never goes out of sync with the
+/// `Action` enum.
+pub fn action_infos() -> Vec<ActionInfo> {
+    let cmd = Action::augment_subcommands(Command::new("action"));
+    cmd.get_subcommands()
+        .map(|sub| ActionInfo {
+            name: sub.get_name().to_owned(),
+            description: sub.get_about().map(|about| about.to_string()),
+            args: sub
+                .get_arguments()
+                .map(|arg| match arg.get_long() {
+                    Some(long) => format!("--{long}"),
+                    None => arg.get_id().to_string(),
+                })
+                .collect(),
+        })
+        .collect()
+}
+
 #[derive(Clone, Debug, clap::ValueEnum)]
 pub enum CompletionShell {
     Bash,
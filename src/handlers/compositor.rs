@@ -23,7 +23,7 @@ use crate::handlers::XDG_ACTIVATION_TOKEN_TIMEOUT;
 use crate::layout::{ActivateWindow, AddWindowTarget, LayoutElement as _};
 use crate::niri::{CastTarget, ClientState, LockState, State};
 use crate::utils::transaction::Transaction;
-use crate::utils::{is_mapped, send_scale_transform};
+use crate::utils::{is_mapped, send_scale_transform, with_toplevel_role};
 use crate::window::{InitialConfigureState, Mapped, ResolvedWindowRules, Unmapped};
 
 impl CompositorHandler for State {
@@ -195,9 +195,19 @@ impl CompositorHandler for State {
                     // The mapped pre-commit hook deals with dma-bufs on its own.
                     self.remove_default_dmabuf_pre_commit_hook(surface);
                     let hook = add_mapped_toplevel_pre_commit_hook(toplevel);
-                    let mapped = Mapped::new(window, rules, hook);
+                    let mut mapped = Mapped::new(window, rules, hook);
                     let window = mapped.window.clone();
 
+                    // Put the window back at its place in the recent windows from before the
+                    // restart.
+                    if let Some(restore) = &mut self.niri.mru_restore {
+                        let app_id =
+                            with_toplevel_role(mapped.toplevel(), |role| role.app_id.clone());
+                        if let Some(rank) = app_id.and_then(|app_id| restore.claim(&app_id)) {
+                            mapped.set_restored_rank(rank);
+                        }
+                    }
+
                     let target = if let Some(p) = &parent {
                         // Open dialogs next to their parent window.
                         AddWindowTarget::NextTo(p)
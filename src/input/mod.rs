@@ -9,7 +9,6 @@ use input::event::gesture::GestureEventCoordinates as _;
 use niri_config::{
     Action, Bind, Binds, Config, Key, ModKey, Modifiers, MruDirection, SwitchBinds, Trigger,
 };
-use niri_ipc::LayoutSwitchTarget;
 use smithay::backend::input::{
     AbsolutePositionEvent, Axis, AxisSource, ButtonState, Device, DeviceCapability, Event,
     GestureBeginEvent, GestureEndEvent, GesturePinchUpdateEvent as _, GestureSwipeUpdateEvent as _,
@@ -19,7 +18,7 @@ use smithay::backend::input::{
     TabletToolTipState, TouchEvent,
 };
 use smithay::backend::libinput::LibinputInputBackend;
-use smithay::input::keyboard::{keysyms, FilterResult, Keysym, Layout, ModifiersState};
+use smithay::input::keyboard::{keysyms, FilterResult, Keysym, ModifiersState};
 use smithay::input::pointer::{
     AxisFrame, ButtonEvent, CursorIcon, CursorImageStatus, Focus, GestureHoldBeginEvent,
     GestureHoldEndEvent, GesturePinchBeginEvent, GesturePinchEndEvent, GesturePinchUpdateEvent,
@@ -46,6 +45,7 @@ use crate::dbus::freedesktop_a11y::KbMonBlock;
 use crate::layout::scrolling::ScrollDirection;
 use crate::layout::{ActivateWindow, LayoutElement as _};
 use crate::niri::{CastTarget, PointerVisibility, State};
+use crate::ui::exit_confirm_dialog::ExitSummary;
 use crate::ui::mru::{WindowMru, WindowMruUi};
 use crate::ui::screenshot_ui::ScreenshotUi;
 use crate::utils::spawning::{spawn, spawn_sh};
@@ -474,9 +474,53 @@ impl State {
                     return FilterResult::Intercept(None);
                 }
 
+                if this.niri.window_label_ui.is_open() && pressed {
+                    if raw == Some(Keysym::Escape) {
+                        this.niri.window_label_ui.close();
+                    } else if raw == Some(Keysym::BackSpace) {
+                        this.niri.window_label_ui.backspace();
+                    } else if let Some(c) = raw.and_then(|raw| raw.key_char()) {
+                        if let Some(id) = this.niri.window_label_ui.type_char(c) {
+                            this.do_action(Action::FocusWindow(id.get()), false);
+                        }
+                    }
+                    this.niri.queue_redraw_all();
+
+                    // Don't send this press to any clients.
+                    this.niri.suppressed_keys.insert(key_code);
+                    return FilterResult::Intercept(None);
+                }
+
+                // While searching in the MRU UI, typed text goes into the search query.
+                if this.niri.window_mru_ui.is_searching() && pressed {
+                    let mut handled = true;
+                    if raw == Some(Keysym::BackSpace) {
+                        this.niri.window_mru_ui.search_backspace();
+                    } else if let Some(c) = modified.key_char().filter(|c| !c.is_control()) {
+                        this.niri.window_mru_ui.search_push(c);
+                    } else {
+                        handled = false;
+                    }
+
+                    if handled {
+                        this.niri.queue_redraw_mru_output();
+
+                        // Don't send this press to any clients.
+                        this.niri.suppressed_keys.insert(key_code);
+                        return FilterResult::Intercept(None);
+                    }
+                }
+
                 // Check if all modifiers were released while the MRU UI was open. If so, close the
                 // UI (which will also transfer the focus to the current MRU UI selection).
-                if this.niri.window_mru_ui.is_open() && !pressed && modifiers.is_empty() {
+                //
+                // When searching, the modifiers are likely released to type, so the UI stays open
+                // until explicitly confirmed or cancelled.
+                if this.niri.window_mru_ui.is_open()
+                    && !this.niri.window_mru_ui.is_searching()
+                    && !pressed
+                    && modifiers.is_empty()
+                {
                     this.do_action(Action::MruConfirm, false);
 
                     if this.niri.suppressed_keys.remove(&key_code) {
@@ -655,9 +699,12 @@ impl State {
 
         match action {
             Action::Quit(skip_confirmation) => {
-                if !skip_confirmation && self.niri.exit_confirm_dialog.show() {
-                    self.niri.queue_redraw_all();
-                    return;
+                if !skip_confirmation {
+                    let summary = ExitSummary::from_layout(&self.niri.layout);
+                    if self.niri.exit_confirm_dialog.show(summary) {
+                        self.niri.queue_redraw_all();
+                        return;
+                    }
                 }
 
                 info!("quitting as requested");
@@ -737,9 +784,20 @@ impl State {
                 self.niri.screenshot_ui.toggle_pointer();
                 self.niri.queue_redraw_all();
             }
+            Action::ScreenshotSelectFocusedWindow => {
+                let Some(output) = self.niri.layout.active_output() else {
+                    return;
+                };
+                let monitor = self.niri.layout.monitor_for_output(output).unwrap();
+                if let Some(rect) = monitor.active_tile_visual_rectangle() {
+                    self.niri.screenshot_ui.select_rect(output, rect);
+                    self.niri.queue_redraw_all();
+                }
+            }
             Action::Screenshot(show_cursor, path) => {
                 self.open_screenshot_ui(show_cursor, path);
                 self.niri.cancel_mru();
+                self.niri.window_label_ui.close();
             }
             Action::ScreenshotWindow(write_to_disk, path) => {
                 let focus = self.niri.layout.focus_with_output();
@@ -859,7 +917,7 @@ impl State {
                     .windows()
                     .map(|(_, win)| win)
                     .filter(|win| Some(win.id()) != current)
-                    .max_by_key(|win| win.get_focus_timestamp())
+                    .max_by_key(|win| win.mru_key())
                     .map(|win| win.window.clone())
                 {
                     // Commit current focus so repeated focus-window-previous works as expected.
@@ -869,19 +927,18 @@ impl State {
                 }
             }
             Action::SwitchLayout(action) => {
-                let keyboard = &self.niri.seat.get_keyboard().unwrap();
-                keyboard.with_xkb_state(self, |mut state| match action {
-                    LayoutSwitchTarget::Next => state.cycle_next_layout(),
-                    LayoutSwitchTarget::Prev => state.cycle_prev_layout(),
-                    LayoutSwitchTarget::Index(layout) => {
-                        let num_layouts = state.xkb().lock().unwrap().layouts().count();
-                        if usize::from(layout) >= num_layouts {
-                            warn!("requested layout doesn't exist")
-                        } else {
-                            state.set_layout(Layout(layout.into()))
-                        }
-                    }
-                });
+                self.switch_keyboard_layout(action);
+            }
+            Action::SwitchWindowLayout { window_id, layout } => {
+                let window = self
+                    .niri
+                    .layout
+                    .windows()
+                    .find(|(_, m)| m.id().get() == window_id);
+                let surface = window.map(|(_, m)| m.toplevel().wl_surface().clone());
+                if let Some(surface) = surface {
+                    self.switch_window_keyboard_layout(&surface, layout);
+                }
             }
             Action::MoveColumnLeft => {
                 if self.niri.screenshot_ui.is_open() {
@@ -1559,6 +1616,18 @@ impl State {
                 // FIXME: granular
                 self.niri.queue_redraw_all();
             }
+            Action::TabifyRow => {
+                self.niri.layout.tabify_all();
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
+            Action::UntabifyRow => {
+                self.niri.layout.untabify_all();
+                self.maybe_warp_cursor_to_focus();
+                // FIXME: granular
+                self.niri.queue_redraw_all();
+            }
             Action::SetColumnDisplay(display) => {
                 self.niri.layout.set_column_display(display);
                 self.maybe_warp_cursor_to_focus();
@@ -2022,6 +2091,15 @@ impl State {
                     self.niri.a11y_announce_hotkey_overlay();
                 }
             }
+            Action::ShowWindowLabels => {
+                if self.niri.screenshot_ui.is_open() || self.niri.window_mru_ui.is_open() {
+                    return;
+                }
+
+                if self.niri.window_label_ui.open(&self.niri.layout) {
+                    self.niri.queue_redraw_all();
+                }
+            }
             Action::MoveWorkspaceToMonitorLeft => {
                 if let Some(output) = self.niri.output_left() {
                     self.niri.layout.move_workspace_to_output(&output);
@@ -2251,6 +2329,14 @@ impl State {
                     self.niri.queue_redraw_all();
                 }
             }
+            Action::ToggleZoomLens => {
+                self.niri.zoom_lens.toggle();
+                self.niri.queue_redraw_all();
+            }
+            Action::ToggleSpotlight => {
+                self.niri.spotlight.toggle();
+                self.niri.queue_redraw_all();
+            }
             Action::ToggleWindowUrgent(id) => {
                 let window = self
                     .niri
@@ -2374,6 +2460,12 @@ impl State {
                     self.niri.queue_redraw_mru_output();
                 }
             }
+            Action::MruStartSearch => {
+                if self.niri.window_mru_ui.is_open() {
+                    self.niri.window_mru_ui.start_search();
+                    self.niri.queue_redraw_mru_output();
+                }
+            }
         }
     }
 
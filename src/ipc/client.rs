@@ -1,32 +1,24 @@
-use std::io::ErrorKind;
+use std::io::{self, BufRead, ErrorKind};
 use std::iter::Peekable;
 use std::path::Path;
 use std::{env, slice};
 
 use anyhow::{anyhow, bail, Context};
+use clap::Parser as _;
 use niri_config::OutputName;
 use niri_ipc::socket::Socket;
 use niri_ipc::{
     Action, Event, KeyboardLayouts, LogicalOutput, Mode, Output, OutputConfigChanged, Overview,
-    Request, Response, Transform, Window, WindowLayout,
+    Request, Response, Transform, Window, WindowKeyboardLayout, WindowLayout,
 };
 use serde_json::json;
 
-use crate::cli::Msg;
+use crate::cli::{BatchLine, Msg};
 use crate::utils::version;
 
 pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
-    // For actions taking paths, prepend the niri CLI's working directory.
-    if let Msg::Action {
-        action:
-            Action::Screenshot { path, .. }
-            | Action::ScreenshotScreen { path, .. }
-            | Action::ScreenshotWindow { path, .. },
-    } = &mut msg
-    {
-        if let Some(path) = path {
-            ensure_absolute_path(path).context("error making the path absolute")?;
-        }
+    if let Msg::Action { action } = &mut msg {
+        make_paths_absolute(action)?;
     }
 
     let request = match &msg {
@@ -37,6 +29,11 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
         Msg::PickWindow => Request::PickWindow,
         Msg::PickColor => Request::PickColor,
         Msg::Action { action } => Request::Action(action.clone()),
+        Msg::Actions => Request::Actions,
+        Msg::Batch => {
+            let actions = parse_batch(io::stdin().lock()).context("error reading actions")?;
+            Request::Batch(actions)
+        }
         Msg::Output { output, action } => Request::Output {
             output: output.clone(),
             action: action.clone(),
@@ -45,6 +42,7 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
         Msg::Windows => Request::Windows,
         Msg::Layers => Request::Layers,
         Msg::KeyboardLayouts => Request::KeyboardLayouts,
+        Msg::WindowKeyboardLayout { id } => Request::WindowKeyboardLayout { id: *id },
         Msg::EventStream => Request::EventStream,
         Msg::RequestError => Request::ReturnError,
         Msg::OverviewState => Request::OverviewState,
@@ -321,6 +319,55 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
                 bail!("unexpected response: expected Handled, got {response:?}");
             };
         }
+        Msg::Actions => {
+            let Response::Actions(actions) = response else {
+                bail!("unexpected response: expected Actions, got {response:?}");
+            };
+
+            if json {
+                let actions =
+                    serde_json::to_string(&actions).context("error formatting response")?;
+                println!("{actions}");
+                return Ok(());
+            }
+
+            for action in actions {
+                let mut line = action.name;
+                for arg in action.args {
+                    line.push(' ');
+                    line.push_str(&arg);
+                }
+                println!("{line}");
+
+                if let Some(description) = action.description {
+                    println!("    {description}");
+                }
+            }
+        }
+        Msg::Batch => {
+            let Response::Batch(results) = response else {
+                bail!("unexpected response: expected Batch, got {response:?}");
+            };
+
+            if json {
+                let results =
+                    serde_json::to_string(&results).context("error formatting response")?;
+                println!("{results}");
+                return Ok(());
+            }
+
+            let mut failed = 0;
+            for (idx, result) in results.iter().enumerate() {
+                if let Err(err) = result {
+                    eprintln!("Action #{} failed: {err}", idx + 1);
+                    failed += 1;
+                }
+            }
+
+            if failed > 0 {
+                bail!("{failed} out of {} actions failed", results.len());
+            }
+        }
         Msg::Output { output, .. } => {
             let Response::OutputConfigChanged(response) = response else {
                 bail!("unexpected response: expected OutputConfigChanged, got {response:?}");
@@ -407,6 +454,21 @@ pub fn handle_msg(mut msg: Msg, json: bool) -> anyhow::Result<()> {
                 println!("{is_active}{idx} {name}");
             }
         }
+        Msg::WindowKeyboardLayout { .. } => {
+            let Response::WindowKeyboardLayout(response) = response else {
+                bail!("unexpected response: expected WindowKeyboardLayout, got {response:?}");
+            };
+
+            if json {
+                let response =
+                    serde_json::to_string(&response).context("error formatting response")?;
+                println!("{response}");
+                return Ok(());
+            }
+
+            let WindowKeyboardLayout { idx, name } = response;
+            println!("Keyboard layout: {idx} {name}");
+        }
         Msg::EventStream => {
             let Response::Handled = response else {
                 bail!("unexpected response: expected Handled, got {response:?}");
@@ -715,6 +777,40 @@ fn fmt_rounded(x: f64) -> String {
     }
 }
 
+/// For actions taking paths, prepends the niri CLI's working directory.
+fn make_paths_absolute(action: &mut Action) -> anyhow::Result<()> {
+    if let Action::Screenshot { path, .. }
+    | Action::ScreenshotScreen { path, .. }
+    | Action::ScreenshotWindow { path, .. } = action
+    {
+        if let Some(path) = path {
+            ensure_absolute_path(path).context("error making the path absolute")?;
+        }
+    }
+    Ok(())
+}
+
+/// Parses `niri msg batch` input: one action per line.
+fn parse_batch(input: impl BufRead) -> anyhow::Result<Vec<Action>> {
+    let mut actions = Vec::new();
+
+    for (idx, line) in input.lines().enumerate() {
+        let line = line?;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut action = BatchLine::try_parse_from(line.split_whitespace())
+            .map_err(|err| anyhow!("line {}: {err}", idx + 1))?
+            .action;
+        make_paths_absolute(&mut action)?;
+        actions.push(action);
+    }
+
+    Ok(actions)
+}
+
 fn ensure_absolute_path(path: &mut String) -> anyhow::Result<()> {
     let p = Path::new(path);
     if p.is_relative() {
@@ -733,6 +829,36 @@ mod tests {
     use insta::assert_snapshot;
 
     use super::*;
+    use crate::cli::action_infos;
+
+    #[test]
+    fn test_parse_batch() {
+        let input = "\
+# Comment.
+focus-workspace 2
+
+  focus-column-first
+set-column-width -10%
+";
+        let actions = parse_batch(input.as_bytes()).unwrap();
+        assert_snapshot!(
+            format!("{actions:?}"),
+            @"[FocusWorkspace { reference: Index(2) }, FocusColumnFirst, SetColumnWidth { change: AdjustProportion(-10.0) }]"
+        );
+
+        let err = parse_batch("focus-column-first\nfocus-nowhere\n".as_bytes()).unwrap_err();
+        assert!(err.to_string().starts_with("line 2: "));
+    }
+
+    #[test]
+    fn test_action_infos() {
+        let actions = action_infos();
+
+        let find = |name| actions.iter().find(|action| action.name == name).unwrap();
+        assert_snapshot!(find("focus-column-first").args.join(" "), @"");
+        assert_snapshot!(find("focus-workspace").args.join(" "), @"reference");
+        assert_snapshot!(find("switch-window-layout").args.join(" "), @"--id layout");
+    }
 
     #[test]
     fn test_fmt_rounded() {
@@ -18,7 +18,7 @@ use niri_config::OutputName;
 use niri_ipc::state::{EventStreamState, EventStreamStatePart as _};
 use niri_ipc::{
     Action, Event, KeyboardLayouts, OutputConfigChanged, Overview, Reply, Request, Response,
-    Timestamp, WindowLayout, Workspace,
+    Timestamp, WindowKeyboardLayout, WindowLayout, Workspace,
 };
 use smithay::desktop::layer_map_for_output;
 use smithay::input::pointer::{
@@ -31,6 +31,7 @@ use smithay::utils::SERIAL_COUNTER;
 use smithay::wayland::shell::wlr_layer::{KeyboardInteractivity, Layer};
 
 use crate::backend::IpcOutputMap;
+use crate::cli::{action_infos, BatchLine};
 use crate::input::pick_window_grab::PickWindowGrab;
 use crate::layout::workspace::WorkspaceId;
 use crate::niri::State;
@@ -334,6 +335,34 @@ async fn process(ctx: &ClientCtx, request: Request) -> Reply {
             let layout = layout.expect("keyboard layouts should be set at startup");
             Response::KeyboardLayouts(layout)
         }
+        Request::WindowKeyboardLayout { id } => {
+            let (tx, rx) = async_channel::bounded(1);
+            ctx.event_loop.insert_idle(move |state| {
+                let window = state
+                    .niri
+                    .layout
+                    .windows()
+                    .find(|(_, m)| m.id().get() == id);
+                let surface = window.map(|(_, m)| m.toplevel().wl_surface().clone());
+                let layout = surface.map(|surface| {
+                    let layout = state.window_keyboard_layout(&surface);
+                    let keyboard = state.niri.seat.get_keyboard().unwrap();
+                    keyboard.with_xkb_state(state, |context| {
+                        let xkb = context.xkb().lock().unwrap();
+                        WindowKeyboardLayout {
+                            idx: layout.0 as u8,
+                            name: xkb.layout_name(layout).to_owned(),
+                        }
+                    })
+                });
+                let _ = tx.send_blocking(layout);
+            });
+            let result = rx.recv().await;
+            let layout =
+                result.map_err(|_| String::from("error getting window keyboard layout"))?;
+            let layout = layout.ok_or_else(|| String::from("window not found"))?;
+            Response::WindowKeyboardLayout(layout)
+        }
         Request::FocusedWindow => {
             let state = ctx.event_stream_state.borrow();
             let windows = &state.windows.windows;
@@ -379,24 +408,39 @@ async fn process(ctx: &ClientCtx, request: Request) -> Reply {
             Response::PickedColor(color)
         }
         Request::Action(action) => {
-            validate_action(&action)?;
+            perform_action(ctx, action).await?;
+            Response::Handled
+        }
+        Request::ActionFromArgs(args) => {
+            let line = BatchLine::try_parse_from(args).map_err(|err| err.to_string())?;
+            perform_action(ctx, line.action).await?;
+            Response::Handled
+        }
+        Request::Actions => Response::Actions(action_infos()),
+        Request::Batch(actions) => {
+            let results = Vec::from_iter(actions.iter().map(validate_action));
 
             let (tx, rx) = async_channel::bounded(1);
 
-            let action = niri_config::Action::from(action);
+            let actions = Vec::from_iter(
+                actions
+                    .into_iter()
+                    .zip(&results)
+                    .filter(|(_, res)| res.is_ok())
+                    .map(|(action, _)| niri_config::Action::from(action)),
+            );
             ctx.event_loop.insert_idle(move |state| {
-                // Make sure some logic like workspace clean-up has a chance to run before doing
-                // actions.
                 state.niri.advance_animations();
-                state.do_action(action, false);
+                // Actions only queue redraws, so doing them all in one callback means that
+                // nothing gets rendered until the last one is done.
+                for action in actions {
+                    state.do_action(action, false);
+                }
                 let _ = tx.send_blocking(());
             });
 
-            // Wait until the action has been processed before returning. This is important for a
-            // few actions, for instance for DoScreenTransition this wait ensures that the screen
-            // contents were sampled into the texture.
             let _ = rx.recv().await;
-            Response::Handled
+            Response::Batch(results)
         }
         Request::Output { output, action } => {
             action.validate()?;
@@ -455,6 +499,27 @@ async fn process(ctx: &ClientCtx, request: Request) -> Reply {
     Ok(response)
 }
 
+async fn perform_action(ctx: &ClientCtx, action: Action) -> Result<(), String> {
+    validate_action(&action)?;
+
+    let (tx, rx) = async_channel::bounded(1);
+
+    let action = niri_config::Action::from(action);
+    ctx.event_loop.insert_idle(move |state| {
+        // Make sure some logic like workspace clean-up has a chance to run before doing
+        // actions.
+        state.niri.advance_animations();
+        state.do_action(action, false);
+        let _ = tx.send_blocking(());
+    });
+
+    // Wait until the action has been processed before returning. This is important for a few
+    // actions, for instance for DoScreenTransition this wait ensures that the screen contents were
+    // sampled into the texture.
+    let _ = rx.recv().await;
+    Ok(())
+}
+
 fn validate_action(action: &Action) -> Result<(), String> {
     if let Action::Screenshot { path, .. }
     | Action::ScreenshotScreen { path, .. }
@@ -2167,6 +2167,20 @@ impl<W: LayoutElement> Layout<W> {
         workspace.expel_from_column();
     }
 
+    pub fn tabify_all(&mut self) {
+        let Some(workspace) = self.active_workspace_mut() else {
+            return;
+        };
+        workspace.tabify_all();
+    }
+
+    pub fn untabify_all(&mut self) {
+        let Some(workspace) = self.active_workspace_mut() else {
+            return;
+        };
+        workspace.untabify_all();
+    }
+
     pub fn swap_window_in_direction(&mut self, direction: ScrollDirection) {
         let Some(workspace) = self.active_workspace_mut() else {
             return;
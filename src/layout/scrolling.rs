@@ -2026,6 +2026,45 @@ impl<W: LayoutElement> ScrollingSpace<W> {
         new_col.tiles[0].animate_move_from(offset);
     }
 
+    /// Moves every window into the first column, in order, and makes that column tabbed.
+    pub fn tabify_all(&mut self) {
+        if self.columns.is_empty() {
+            return;
+        }
+
+        let col = &self.columns[self.active_column_idx];
+        let active = col.tiles[col.active_tile_idx].window().id().clone();
+
+        self.activate_column(0);
+        while self.columns.len() > 1 {
+            self.consume_into_column();
+        }
+        self.set_column_display(ColumnDisplay::Tabbed);
+
+        self.activate_window(&active);
+    }
+
+    /// Spreads the windows out so that every column holds exactly one, keeping their order.
+    pub fn untabify_all(&mut self) {
+        if self.columns.is_empty() {
+            return;
+        }
+
+        let col = &self.columns[self.active_column_idx];
+        let active = col.tiles[col.active_tile_idx].window().id().clone();
+
+        // Go right to left so that expelled windows don't shift the columns still to visit.
+        for idx in (0..self.columns.len()).rev() {
+            self.activate_column(idx);
+            self.set_column_display(ColumnDisplay::Normal);
+            while self.columns[idx].tiles.len() > 1 {
+                self.expel_from_column();
+            }
+        }
+
+        self.activate_window(&active);
+    }
+
     pub fn swap_window_in_direction(&mut self, direction: ScrollDirection) {
         if self.columns.is_empty() {
             return;
@@ -515,6 +515,8 @@ enum Op {
     ExpelWindowFromColumn,
     SwapWindowInDirection(#[proptest(strategy = "arbitrary_scroll_direction()")] ScrollDirection),
     ToggleColumnTabbedDisplay,
+    TabifyAll,
+    UntabifyAll,
     SetColumnDisplay(#[proptest(strategy = "arbitrary_column_display()")] ColumnDisplay),
     CenterColumn,
     CenterWindow {
@@ -1177,6 +1179,8 @@ impl Op {
             Op::ExpelWindowFromColumn => layout.expel_from_column(),
             Op::SwapWindowInDirection(direction) => layout.swap_window_in_direction(direction),
             Op::ToggleColumnTabbedDisplay => layout.toggle_column_tabbed_display(),
+            Op::TabifyAll => layout.tabify_all(),
+            Op::UntabifyAll => layout.untabify_all(),
             Op::SetColumnDisplay(display) => layout.set_column_display(display),
             Op::CenterColumn => layout.center_column(),
             Op::CenterWindow { id } => {
@@ -3635,6 +3639,53 @@ fn move_window_to_workspace_maximize_and_fullscreen() {
     assert_eq!(win.pending_sizing_mode(), SizingMode::Normal);
 }
 
+#[test]
+fn tabify_all_keeps_order_and_focus() {
+    let ops = [
+        Op::AddOutput(1),
+        Op::AddWindow {
+            params: TestWindowParams::new(1),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(2),
+        },
+        Op::AddWindow {
+            params: TestWindowParams::new(3),
+        },
+        Op::FocusColumnLeft,
+        Op::ConsumeWindowIntoColumn,
+        Op::FocusColumnLeft,
+        Op::TabifyAll,
+    ];
+
+    let mut layout = check_ops(ops);
+
+    let positions = |layout: &Layout<TestWindow>| {
+        let scrolling = layout.active_workspace().unwrap().scrolling();
+        Vec::from_iter(
+            scrolling
+                .tiles_with_ipc_layouts()
+                .map(|(tile, win_layout)| {
+                    (*tile.window().id(), win_layout.pos_in_scrolling_layout)
+                }),
+        )
+    };
+
+    assert_eq!(
+        positions(&layout),
+        [(1, Some((1, 1))), (2, Some((1, 2))), (3, Some((1, 3)))]
+    );
+    assert_eq!(layout.focus().map(|win| *win.id()), Some(1));
+
+    check_ops_on_layout(&mut layout, [Op::FocusWindowDown, Op::UntabifyAll]);
+
+    assert_eq!(
+        positions(&layout),
+        [(1, Some((1, 1))), (2, Some((2, 1))), (3, Some((3, 1)))]
+    );
+    assert_eq!(layout.focus().map(|win| *win.id()), Some(2));
+}
+
 #[test]
 fn tabs_with_different_border() {
     let ops = [
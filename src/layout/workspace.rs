@@ -1127,6 +1127,14 @@ impl<W: LayoutElement> Workspace<W> {
         self.scrolling.expel_from_column();
     }
 
+    pub fn tabify_all(&mut self) {
+        self.scrolling.tabify_all();
+    }
+
+    pub fn untabify_all(&mut self) {
+        self.scrolling.untabify_all();
+    }
+
     pub fn swap_window_in_direction(&mut self, direction: ScrollDirection) {
         if self.floating_is_active.get() {
             return;
@@ -1,4 +1,5 @@
 use std::cell::{Cell, OnceCell, RefCell};
+use std::cmp::Reverse;
 use std::collections::{HashMap, HashSet};
 use std::ffi::OsString;
 use std::os::unix::net::UnixStream;
@@ -18,6 +19,7 @@ use niri_config::{
     Config, FloatOrInt, Key, Modifiers, OutputName, TrackLayout, WarpMouseToFocusMode,
     WorkspaceReference, Xkb,
 };
+use niri_ipc::LayoutSwitchTarget;
 use smithay::backend::allocator::Fourcc;
 use smithay::backend::input::Keycode;
 use smithay::backend::renderer::damage::OutputDamageTracker;
@@ -165,9 +167,14 @@ use crate::render_helpers::{
 use crate::ui::config_error_notification::ConfigErrorNotification;
 use crate::ui::exit_confirm_dialog::{ExitConfirmDialog, ExitConfirmDialogRenderElement};
 use crate::ui::hotkey_overlay::HotkeyOverlay;
+use crate::ui::lock_summary::LockSummary;
+use crate::ui::mru::persist::{self, MruRestore};
 use crate::ui::mru::{MruCloseRequest, WindowMruUi, WindowMruUiRenderElement};
 use crate::ui::screen_transition::{self, ScreenTransition};
 use crate::ui::screenshot_ui::{OutputScreenshot, ScreenshotUi, ScreenshotUiRenderElement};
+use crate::ui::spotlight::Spotlight;
+use crate::ui::window_labels::WindowLabelUi;
+use crate::ui::zoom_lens::{ZoomLens, ZoomLensRenderElement};
 use crate::utils::scale::{closest_representable_scale, guess_monitor_scale};
 use crate::utils::spawning::{CHILD_DISPLAY, CHILD_ENV};
 use crate::utils::vblank_throttle::VBlankThrottle;
@@ -176,7 +183,7 @@ use crate::utils::xwayland::satellite::Satellite;
 use crate::utils::{
     center, center_f64, expand_home, get_monotonic_time, ipc_transform_to_smithay, is_mapped,
     logical_output, make_screenshot_path, output_matches_name, output_size, panel_orientation,
-    send_scale_transform, write_png_rgba8, xwayland,
+    send_scale_transform, with_toplevel_role, write_png_rgba8, xwayland,
 };
 use crate::window::mapped::MappedId;
 use crate::window::{InitialConfigureState, Mapped, ResolvedWindowRules, Unmapped, WindowRef};
@@ -376,6 +383,8 @@ pub struct Niri {
     pub mods_with_finger_scroll_binds: HashSet<Modifiers>,
 
     pub lock_state: LockState,
+    pub lock_summary: LockSummary,
+    pub lock_summary_timer: Option<RegistrationToken>,
 
     // State that we last sent to the logind LockedHint.
     pub locked_hint: Option<bool>,
@@ -387,6 +396,13 @@ pub struct Niri {
 
     pub window_mru_ui: WindowMruUi,
     pub pending_mru_commit: Option<PendingMruCommit>,
+    pub mru_restore: Option<MruRestore>,
+    pub pending_mru_save: Option<RegistrationToken>,
+
+    pub window_label_ui: WindowLabelUi,
+
+    pub zoom_lens: ZoomLens,
+    pub spotlight: Spotlight,
 
     pub pick_window: Option<async_channel::Sender<Option<MappedId>>>,
     pub pick_color: Option<async_channel::Sender<Option<niri_ipc::PickedColor>>>,
@@ -526,6 +542,7 @@ pub enum KeyboardFocus {
     ExitConfirmDialog,
     Overview,
     Mru,
+    WindowLabels,
 }
 
 #[derive(Default, Clone, PartialEq)]
@@ -635,6 +652,7 @@ impl KeyboardFocus {
             KeyboardFocus::ExitConfirmDialog => None,
             KeyboardFocus::Overview => None,
             KeyboardFocus::Mru => None,
+            KeyboardFocus::WindowLabels => None,
         }
     }
 
@@ -647,6 +665,7 @@ impl KeyboardFocus {
             KeyboardFocus::ExitConfirmDialog => None,
             KeyboardFocus::Overview => None,
             KeyboardFocus::Mru => None,
+            KeyboardFocus::WindowLabels => None,
         }
     }
 
@@ -779,6 +798,7 @@ impl State {
         self.niri.refresh_pointer_outputs();
         self.niri.global_space.refresh();
         self.niri.refresh_idle_inhibit();
+        self.niri.refresh_lock_summary();
         self.refresh_pointer_contents();
         foreign_toplevel::refresh(self);
         ext_workspace::refresh(self);
@@ -1123,6 +1143,8 @@ impl State {
             KeyboardFocus::ScreenshotUi
         } else if self.niri.window_mru_ui.is_open() {
             KeyboardFocus::Mru
+        } else if self.niri.window_label_ui.is_open() {
+            KeyboardFocus::WindowLabels
         } else if let Some(output) = self.niri.layout.active_output() {
             let mon = self.niri.layout.monitor_for_output(output).unwrap();
             let layers = layer_map_for_output(output);
@@ -1241,6 +1263,7 @@ impl State {
                     mapped.set_is_focused(false);
                 }
             }
+            let mut mru_changed = false;
             if let KeyboardFocus::Layout {
                 surface: Some(surface),
             } = &focus
@@ -1260,6 +1283,7 @@ impl State {
 
                     if mapped.get_focus_timestamp().is_none() || debounce.is_zero() {
                         mapped.set_focus_timestamp(stamp);
+                        mru_changed = true;
                     } else {
                         let timer = Timer::from_duration(debounce);
 
@@ -1283,6 +1307,9 @@ impl State {
                     }
                 }
             }
+            if mru_changed {
+                self.niri.queue_mru_save();
+            }
 
             if let Some(grab) = self.niri.popup_grab.as_mut() {
                 if grab.has_keyboard_grab && Some(&grab.root) != focus.surface() {
@@ -1346,6 +1373,89 @@ impl State {
         }
     }
 
+    pub fn switch_keyboard_layout(&mut self, target: LayoutSwitchTarget) {
+        let keyboard = &self.niri.seat.get_keyboard().unwrap();
+        keyboard.with_xkb_state(self, |mut state| match target {
+            LayoutSwitchTarget::Next => state.cycle_next_layout(),
+            LayoutSwitchTarget::Prev => state.cycle_prev_layout(),
+            LayoutSwitchTarget::Index(layout) => {
+                let num_layouts = state.xkb().lock().unwrap().layouts().count();
+                if usize::from(layout) >= num_layouts {
+                    warn!("requested layout doesn't exist")
+                } else {
+                    state.set_layout(KeyboardLayout(layout.into()))
+                }
+            }
+        });
+    }
+
+    /// Returns the keyboard layout of the window with this surface.
+    pub fn window_keyboard_layout(&mut self, surface: &WlSurface) -> KeyboardLayout {
+        let is_focused = self.niri.keyboard_focus.surface() == Some(surface);
+        let per_window =
+            self.niri.config.borrow().input.keyboard.track_layout == TrackLayout::Window;
+
+        // The focused window's layout is only stored once it loses focus.
+        if is_focused || !per_window {
+            let keyboard = self.niri.seat.get_keyboard().unwrap();
+            return keyboard.with_xkb_state(self, |context| {
+                let xkb = context.xkb().lock().unwrap();
+                xkb.active_layout()
+            });
+        }
+
+        // Windows that were never focused will get the default layout.
+        with_states(surface, |data| {
+            data.data_map
+                .get::<Cell<KeyboardLayout>>()
+                .map_or_else(KeyboardLayout::default, Cell::get)
+        })
+    }
+
+    /// Switches the keyboard layout of the window with this surface.
+    pub fn switch_window_keyboard_layout(
+        &mut self,
+        surface: &WlSurface,
+        target: LayoutSwitchTarget,
+    ) {
+        let is_focused = self.niri.keyboard_focus.surface() == Some(surface);
+        let per_window =
+            self.niri.config.borrow().input.keyboard.track_layout == TrackLayout::Window;
+
+        if is_focused || !per_window {
+            self.switch_keyboard_layout(target);
+            return;
+        }
+
+        let keyboard = self.niri.seat.get_keyboard().unwrap();
+        let num_layouts = keyboard.with_xkb_state(self, |context| {
+            let xkb = context.xkb().lock().unwrap();
+            xkb.layouts().count() as u32
+        });
+
+        let current = self.window_keyboard_layout(surface).0;
+        let new = match target {
+            LayoutSwitchTarget::Next => (current + 1) % num_layouts,
+            LayoutSwitchTarget::Prev => (current + num_layouts - 1) % num_layouts,
+            LayoutSwitchTarget::Index(layout) => {
+                let layout = u32::from(layout);
+                if layout >= num_layouts {
+                    warn!("requested layout doesn't exist");
+                    return;
+                }
+                layout
+            }
+        };
+
+        // Picked up by update_keyboard_focus() when the window is focused.
+        with_states(surface, |data| {
+            let cell = data
+                .data_map
+                .get_or_insert::<Cell<KeyboardLayout>, _>(Cell::default);
+            cell.set(KeyboardLayout(new));
+        });
+    }
+
     /// Loads the xkb keymap from a file config setting.
     fn set_xkb_file(&mut self, xkb_file: String) -> anyhow::Result<()> {
         let xkb_file = PathBuf::from(xkb_file);
@@ -2351,8 +2461,6 @@ impl State {
         to_introspect: &async_channel::Sender<NiriToIntrospect>,
         msg: IntrospectToNiri,
     ) {
-        use crate::utils::with_toplevel_role;
-
         let IntrospectToNiri::GetWindows = msg;
         let _span = tracy_client::span!("GetWindows");
 
@@ -2606,6 +2714,11 @@ impl Niri {
 
         let screenshot_ui = ScreenshotUi::new(animation_clock.clone(), config.clone());
         let window_mru_ui = WindowMruUi::new(config.clone());
+        let mru_restore = if config_.recent_windows.persist_order {
+            MruRestore::load()
+        } else {
+            None
+        };
         let config_error_notification =
             ConfigErrorNotification::new(animation_clock.clone(), config.clone());
 
@@ -2615,6 +2728,7 @@ impl Niri {
         }
 
         let exit_confirm_dialog = ExitConfirmDialog::new(animation_clock.clone(), config.clone());
+        let spotlight = Spotlight::new(animation_clock.clone(), config.clone());
 
         #[cfg(feature = "dbus")]
         let a11y = A11y::new(event_loop.clone());
@@ -2681,6 +2795,9 @@ impl Niri {
                 |_, _, state| {
                     let _span = tracy_client::span!("startup timeout");
                     state.niri.is_at_startup = false;
+                    // Windows opened from now on are new rather than coming back after a
+                    // restart.
+                    state.niri.mru_restore = None;
                     state.niri.recompute_window_rules();
                     state.niri.recompute_layer_rules();
                     TimeoutAction::Drop
@@ -2799,6 +2916,8 @@ impl Niri {
             mods_with_finger_scroll_binds,
 
             lock_state: LockState::Unlocked,
+            lock_summary: LockSummary::new(),
+            lock_summary_timer: None,
             locked_hint: None,
 
             screenshot_ui,
@@ -2808,6 +2927,13 @@ impl Niri {
 
             window_mru_ui,
             pending_mru_commit: None,
+            mru_restore,
+            pending_mru_save: None,
+
+            window_label_ui: WindowLabelUi::new(),
+
+            zoom_lens: ZoomLens::new(config.clone()),
+            spotlight,
 
             pick_window: None,
             pick_color: None,
@@ -3880,6 +4006,32 @@ impl Niri {
         }
     }
 
+    fn render_zoom_lens<R: NiriRenderer>(
+        &self,
+        renderer: &mut R,
+        output: &Output,
+        target: RenderTarget,
+    ) -> Vec<OutputRenderElements<R>> {
+        let output_geo = self.global_space.output_geometry(output).unwrap();
+        let pointer_pos = self
+            .tablet_cursor_location
+            .unwrap_or_else(|| self.seat.get_pointer().unwrap().current_location());
+        if !output_geo.to_f64().contains(pointer_pos) {
+            return vec![];
+        }
+        let pointer_pos = pointer_pos - output_geo.loc.to_f64();
+
+        let renderer = renderer.as_gles_renderer();
+        let contents = self.render::<GlesRenderer>(renderer, output, false, target);
+
+        let scale = output.current_scale().fractional_scale();
+        self.zoom_lens
+            .render(renderer, &contents, pointer_pos, scale)
+            .into_iter()
+            .map(OutputRenderElements::from)
+            .collect()
+    }
+
     pub fn pointer_element<R: NiriRenderer>(
         &self,
         renderer: &mut R,
@@ -4132,6 +4284,7 @@ impl Niri {
             KeyboardFocus::ExitConfirmDialog => true,
             KeyboardFocus::Overview => true,
             KeyboardFocus::Mru => true,
+            KeyboardFocus::WindowLabels => true,
         };
 
         self.layout.refresh(layout_is_active);
@@ -4151,6 +4304,44 @@ impl Niri {
         self.idle_notifier_state.set_is_inhibited(is_inhibited);
     }
 
+    pub fn refresh_lock_summary(&mut self) {
+        let show = self.is_locked()
+            && self.config.borrow().lock_screen.show_summary
+            && self
+                .output_state
+                .values()
+                .any(|state| state.lock_surface.is_none());
+        if !show {
+            if let Some(token) = self.lock_summary_timer.take() {
+                self.event_loop.remove(token);
+            }
+            return;
+        }
+
+        let windows = self.layout.windows().count();
+        if self.lock_summary_timer.is_some() && self.lock_summary.windows() == windows {
+            return;
+        }
+
+        if let Some(token) = self.lock_summary_timer.take() {
+            self.event_loop.remove(token);
+        }
+
+        // Update once a minute for the clock, and right away when the window count changes.
+        let until_next = self.lock_summary.update(windows);
+        let timer = Timer::from_duration(until_next);
+        let token = self
+            .event_loop
+            .insert_source(timer, |_, _, state| {
+                state.niri.lock_summary_timer = None;
+                state.niri.queue_redraw_all();
+                TimeoutAction::Drop
+            })
+            .unwrap();
+        self.lock_summary_timer = Some(token);
+        self.queue_redraw_all();
+    }
+
     pub fn refresh_window_states(&mut self) {
         let _span = tracy_client::span!("Niri::refresh_window_states");
 
@@ -4265,6 +4456,25 @@ impl Niri {
         self.screenshot_ui.advance_animations();
         self.window_mru_ui.advance_animations();
 
+        if self.spotlight.is_open() {
+            let target = self.layout.active_output().and_then(|output| {
+                let mon = self.layout.monitor_for_output(output)?;
+                let window = mon.active_window()?.id();
+                let mut rect = mon.active_tile_visual_rectangle()?;
+
+                // The rectangle is relative to the workspace, which moves during a switch.
+                let ws_id = mon.active_workspace_ref().id();
+                let (_, ws_geo) = mon
+                    .workspaces_with_render_geo()
+                    .find(|(ws, _)| ws.id() == ws_id)?;
+                rect.loc += ws_geo.loc;
+
+                Some((output.clone(), window, rect))
+            });
+            self.spotlight.update(target);
+            self.spotlight.advance_animations();
+        }
+
         for state in self.output_state.values_mut() {
             if let Some(transition) = &mut state.screen_transition {
                 if transition.is_done() {
@@ -4333,6 +4543,12 @@ impl Niri {
         let mut elements = vec![];
         if include_pointer {
             elements = self.pointer_element(renderer, output);
+
+            // Then, the zoom lens under it. The lens contents are rendered without the pointer,
+            // which also keeps this from recursing.
+            if self.zoom_lens.is_open() {
+                elements.extend(self.render_zoom_lens(renderer, output, target));
+            }
         }
 
         // Next, the screen transition texture.
@@ -4368,6 +4584,10 @@ impl Niri {
                     1.,
                     Kind::ScanoutCandidate,
                 ));
+            } else if self.config.borrow().lock_screen.show_summary {
+                if let Some(element) = self.lock_summary.render(renderer, output) {
+                    elements.push(element.into());
+                }
             }
 
             // Draw the solid color background.
@@ -4420,6 +4640,14 @@ impl Niri {
             elements.push(element.into());
         }
 
+        // Then, the window labels.
+        let label_elements = self
+            .window_label_ui
+            .render(renderer, &self.layout, output)
+            .into_iter()
+            .map(OutputRenderElements::from);
+        elements.extend(label_elements);
+
         // Then, the Alt-Tab switcher.
         let mru_elements = self
             .window_mru_ui
@@ -4429,6 +4657,16 @@ impl Niri {
             .map(OutputRenderElements::from);
         elements.extend(mru_elements);
 
+        // Then, the spotlight dimming everything but the focused window.
+        if !self.layout.is_overview_open() {
+            let spotlight_elements = self
+                .spotlight
+                .render(output)
+                .into_iter()
+                .map(OutputRenderElements::from);
+            elements.extend(spotlight_elements);
+        }
+
         // Don't draw the focus ring on the workspaces while interactively moving above those
         // workspaces, since the interactively-moved window already has a focus ring.
         let focus_ring = !self.layout.interactive_move_is_moving_above_output(output);
@@ -4621,6 +4859,7 @@ impl Niri {
             state.unfinished_animations_remain |= self.exit_confirm_dialog.are_animations_ongoing();
             state.unfinished_animations_remain |= self.screenshot_ui.are_animations_ongoing();
             state.unfinished_animations_remain |= self.window_mru_ui.are_animations_ongoing();
+            state.unfinished_animations_remain |= self.spotlight.are_animations_ongoing();
             state.unfinished_animations_remain |= state.screen_transition.is_some();
 
             // Also keep redrawing if the current cursor is animated.
@@ -5945,6 +6184,7 @@ impl Niri {
         if self.output_state.is_empty() {
             // There are no outputs, lock the session right away.
             self.screenshot_ui.close();
+            self.window_label_ui.close();
             self.cursor_manager
                 .set_cursor_image(CursorImageStatus::default_named());
 
@@ -6008,6 +6248,7 @@ impl Niri {
                 self.cursor_manager
                     .set_cursor_image(CursorImageStatus::default_named());
                 self.cancel_mru();
+                self.window_label_ui.close();
 
                 if self.output_state.is_empty() {
                     // There are no outputs, lock the session right away.
@@ -6528,6 +6769,60 @@ impl Niri {
             .find(|w| w.id() == pending.id)
         {
             window.set_focus_timestamp(pending.stamp);
+            self.queue_mru_save();
+        }
+    }
+
+    /// Schedules saving the recent windows order to disk, if enabled.
+    pub fn queue_mru_save(&mut self) {
+        if !self.config.borrow().recent_windows.persist_order || self.pending_mru_save.is_some() {
+            return;
+        }
+
+        // Coalesce bursts of focus changes into a single write.
+        let timer = Timer::from_duration(Duration::from_secs(2));
+        let token = self
+            .event_loop
+            .insert_source(timer, |_, _, state| {
+                state.niri.pending_mru_save = None;
+                state.niri.save_mru_order();
+                TimeoutAction::Drop
+            })
+            .unwrap();
+        self.pending_mru_save = Some(token);
+    }
+
+    /// Saves the recent windows order to disk right away, if enabled.
+    pub fn save_mru_order(&mut self) {
+        if let Some(token) = self.pending_mru_save.take() {
+            self.event_loop.remove(token);
+        }
+
+        if !self.config.borrow().recent_windows.persist_order {
+            return;
+        }
+
+        let _span = tracy_client::span!("Niri::save_mru_order");
+
+        let mut windows = Vec::from_iter(
+            self.layout
+                .windows()
+                .map(|(_, mapped)| mapped)
+                .filter(|mapped| mapped.mru_key() != (None, None)),
+        );
+        windows.sort_by_key(|mapped| Reverse(mapped.mru_key()));
+
+        let mut app_ids = Vec::from_iter(windows.into_iter().filter_map(|mapped| {
+            with_toplevel_role(mapped.toplevel(), |role| role.app_id.clone())
+        }));
+
+        // Keep the entries of windows that haven't reopened yet since the restart.
+        if let Some(restore) = &self.mru_restore {
+            app_ids.extend(restore.unclaimed().map(String::from));
+        }
+
+        if let Err(err) = persist::save(app_ids) {
+            warn!("error saving recent windows order: {err:?}");
         }
     }
 
@@ -6585,6 +6880,7 @@ niri_render_elements! {
         ScreenshotUi = ScreenshotUiRenderElement,
         WindowMruUi = WindowMruUiRenderElement<R>,
         ExitConfirmDialog = ExitConfirmDialogRenderElement,
+        ZoomLens = ZoomLensRenderElement,
         Texture = PrimaryGpuTextureRenderElement,
         // Used for the CPU-rendered panels.
         RelocatedMemoryBuffer = RelocateRenderElement<MemoryRenderBufferRenderElement<R>>,
@@ -8,7 +8,9 @@ use smithay::backend::renderer::element::utils::{Relocate, RelocateRenderElement
 use smithay::backend::renderer::element::{Kind, RenderElement};
 use smithay::backend::renderer::gles::{GlesMapping, GlesRenderer, GlesTarget, GlesTexture};
 use smithay::backend::renderer::sync::SyncPoint;
-use smithay::backend::renderer::{Bind, Color32F, ExportMem, Frame, Offscreen, Renderer};
+use smithay::backend::renderer::{
+    Bind, Color32F, ExportMem, Frame, Offscreen, Renderer, Texture as _,
+};
 use smithay::reexports::wayland_server::protocol::wl_buffer::WlBuffer;
 use smithay::reexports::wayland_server::protocol::wl_shm;
 use smithay::utils::{Logical, Physical, Point, Rectangle, Scale, Size, Transform};
@@ -213,6 +215,24 @@ pub fn render_to_texture(
     Ok((texture, sync_point))
 }
 
+/// Renders elements into an existing texture, covering all of it.
+pub fn render_to_existing_texture(
+    renderer: &mut GlesRenderer,
+    texture: &mut GlesTexture,
+    scale: Scale<f64>,
+    transform: Transform,
+    elements: impl Iterator<Item = impl RenderElement<GlesRenderer>>,
+) -> anyhow::Result<SyncPoint> {
+    let _span = tracy_client::span!();
+
+    let size = texture
+        .size()
+        .to_logical(1, Transform::Normal)
+        .to_physical(1);
+    let mut target = renderer.bind(texture).context("error binding texture")?;
+    render_elements(renderer, &mut target, size, scale, transform, elements)
+}
+
 pub fn render_and_download(
     renderer: &mut GlesRenderer,
     size: Size<i32, Physical>,
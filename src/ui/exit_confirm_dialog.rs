@@ -15,6 +15,7 @@ use smithay::reexports::gbm::Format as Fourcc;
 use smithay::utils::{Point, Transform};
 
 use crate::animation::{Animation, Clock};
+use crate::layout::Layout;
 use crate::niri_render_elements;
 use crate::render_helpers::memory::MemoryBuffer;
 use crate::render_helpers::primary_gpu_texture::PrimaryGpuTextureRenderElement;
@@ -22,6 +23,7 @@ use crate::render_helpers::renderer::NiriRenderer;
 use crate::render_helpers::solid_color::{SolidColorBuffer, SolidColorRenderElement};
 use crate::render_helpers::texture::{TextureBuffer, TextureRenderElement};
 use crate::utils::{output_size, to_physical_precise_round};
+use crate::window::Mapped;
 
 const KEY_NAME: &str = "Enter";
 const PADDING: i32 = 16;
@@ -31,6 +33,7 @@ const BACKDROP_COLOR: [f32; 4] = [0., 0., 0., 0.4];
 
 pub struct ExitConfirmDialog {
     state: State,
+    summary: ExitSummary,
     buffers: RefCell<HashMap<NotNan<f64>, Option<MemoryBuffer>>>,
 
     clock: Clock,
@@ -55,19 +58,20 @@ enum State {
     Hiding(Animation),
 }
 
+/// What exiting will close, shown in the dialog.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ExitSummary {
+    pub windows: usize,
+    pub workspaces: usize,
+}
+
 impl ExitConfirmDialog {
     pub fn new(clock: Clock, config: Rc<RefCell<Config>>) -> Self {
-        let buffer = match render(1.) {
-            Ok(x) => Some(x),
-            Err(err) => {
-                warn!("error creating the exit confirm dialog: {err:?}");
-                None
-            }
-        };
-
+        let summary = ExitSummary::default();
         Self {
             state: State::Hidden,
-            buffers: RefCell::new(HashMap::from([(NotNan::new(1.).unwrap(), buffer)])),
+            summary,
+            buffers: RefCell::new(fallback_buffers(summary)),
             clock,
             config,
         }
@@ -98,16 +102,32 @@ impl ExitConfirmDialog {
         }
     }
 
-    /// Returns true if the dialog will be shown (even if it is already shown).
-    pub fn show(&mut self) -> bool {
-        if !self.can_show() {
-            return false;
-        }
+    #[cfg(feature = "dbus")]
+    pub fn a11y_node(&self) -> accesskit::Node {
+        let mut node = accesskit::Node::new(accesskit::Role::AlertDialog);
+        node.set_label("Exit niri");
+        node.set_description(text(false, self.summary));
+        node.set_modal();
+        node
+    }
 
+    /// Returns true if the dialog will be shown (even if it is already shown).
+    ///
+    /// The summary is only updated if the dialog wasn't already shown.
+    pub fn show(&mut self, summary: ExitSummary) -> bool {
         if self.is_open() {
             return true;
         }
 
+        if self.summary != summary {
+            self.summary = summary;
+            self.buffers = RefCell::new(fallback_buffers(summary));
+        }
+
+        if !self.can_show() {
+            return false;
+        }
+
         self.state = State::Showing(self.animation(self.value(), 1.));
         true
     }
@@ -173,7 +193,7 @@ impl ExitConfirmDialog {
 
         let buffer = buffers
             .entry(NotNan::new(scale).unwrap())
-            .or_insert_with(|| render(scale).ok());
+            .or_insert_with(|| render(scale, self.summary).ok());
         let buffer = buffer.as_ref().unwrap_or(&fallback);
 
         let size = buffer.logical_size();
@@ -224,10 +244,35 @@ impl ExitConfirmDialog {
     }
 }
 
-fn render(scale: f64) -> anyhow::Result<MemoryBuffer> {
+impl ExitSummary {
+    pub fn from_layout(layout: &Layout<Mapped>) -> Self {
+        Self {
+            windows: layout.windows().count(),
+            workspaces: layout
+                .workspaces()
+                .filter(|(_, _, ws)| ws.has_windows())
+                .count(),
+        }
+    }
+}
+
+/// Creates the buffer cache with the scale 1 buffer, which is used as the fallback.
+fn fallback_buffers(summary: ExitSummary) -> HashMap<NotNan<f64>, Option<MemoryBuffer>> {
+    let buffer = match render(1., summary) {
+        Ok(x) => Some(x),
+        Err(err) => {
+            warn!("error creating the exit confirm dialog: {err:?}");
+            None
+        }
+    };
+
+    HashMap::from([(NotNan::new(1.).unwrap(), buffer)])
+}
+
+fn render(scale: f64, summary: ExitSummary) -> anyhow::Result<MemoryBuffer> {
     let _span = tracy_client::span!("exit_confirm_dialog::render");
 
-    let markup = text(true);
+    let markup = text(true, summary);
 
     let padding: i32 = to_physical_precise_round(scale, PADDING);
 
@@ -284,24 +329,77 @@ fn render(scale: f64) -> anyhow::Result<MemoryBuffer> {
     Ok(buffer)
 }
 
-fn text(markup: bool) -> String {
+fn text(markup: bool, summary: ExitSummary) -> String {
     let key = if markup {
         format!("<span face='mono' bgcolor='#2C2C2C'> {KEY_NAME} </span>")
     } else {
         String::from(KEY_NAME)
     };
 
+    let ExitSummary {
+        windows,
+        workspaces,
+    } = summary;
+    let lost = if windows == 0 {
+        String::new()
+    } else {
+        let windows = plural(windows, "window");
+        let workspaces = plural(workspaces, "workspace");
+        format!("{windows} across {workspaces} will be closed.\n\n")
+    };
+
     format!(
         "Are you sure you want to exit niri?\n\n\
-         Press {key} to confirm."
+         {lost}Press {key} to confirm."
     )
 }
 
-#[cfg(feature = "dbus")]
-pub fn a11y_node() -> accesskit::Node {
-    let mut node = accesskit::Node::new(accesskit::Role::AlertDialog);
-    node.set_label("Exit niri");
-    node.set_description(text(false));
-    node.set_modal();
-    node
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {noun}")
+    } else {
+        format!("{count} {noun}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    use super::*;
+
+    #[test]
+    fn test_text() {
+        let summary = |windows, workspaces| ExitSummary {
+            windows,
+            workspaces,
+        };
+
+        assert_snapshot!(text(false, summary(0, 0)), @r"
+        Are you sure you want to exit niri?
+
+        Press Enter to confirm.
+        ");
+        assert_snapshot!(text(false, summary(1, 1)), @r"
+        Are you sure you want to exit niri?
+
+        1 window across 1 workspace will be closed.
+
+        Press Enter to confirm.
+        ");
+        assert_snapshot!(text(false, summary(2, 1)), @r"
+        Are you sure you want to exit niri?
+
+        2 windows across 1 workspace will be closed.
+
+        Press Enter to confirm.
+        ");
+        assert_snapshot!(text(false, summary(12, 4)), @r"
+        Are you sure you want to exit niri?
+
+        12 windows across 4 workspaces will be closed.
+
+        Press Enter to confirm.
+        ");
+    }
 }
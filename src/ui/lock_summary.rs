@@ -0,0 +1,175 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use ordered_float::NotNan;
+use pangocairo::cairo::{self, ImageSurface};
+use pangocairo::pango::{Alignment, FontDescription, Layout};
+use smithay::backend::renderer::element::Kind;
+use smithay::output::Output;
+use smithay::reexports::gbm::Format as Fourcc;
+use smithay::utils::Transform;
+
+use crate::render_helpers::memory::MemoryBuffer;
+use crate::render_helpers::primary_gpu_texture::PrimaryGpuTextureRenderElement;
+use crate::render_helpers::renderer::NiriRenderer;
+use crate::render_helpers::texture::{TextureBuffer, TextureRenderElement};
+use crate::utils::{local_time, output_size, to_physical_precise_round};
+
+const CLOCK_FONT: &str = "sans 64px";
+const FONT: &str = "sans 16px";
+const GAP: i32 = 8;
+
+/// Built-in lock screen showing the time and how many windows are locked.
+///
+/// Drawn on outputs without a lock surface. It never shows any window contents.
+pub struct LockSummary {
+    clock: String,
+    windows: usize,
+    buffers: RefCell<HashMap<NotNan<f64>, Option<MemoryBuffer>>>,
+}
+
+impl LockSummary {
+    pub fn new() -> Self {
+        Self {
+            clock: String::new(),
+            windows: 0,
+            buffers: RefCell::new(HashMap::new()),
+        }
+    }
+
+    pub fn windows(&self) -> usize {
+        self.windows
+    }
+
+    /// Updates the shown time and window count.
+    ///
+    /// Returns the time until the clock should be updated again.
+    pub fn update(&mut self, windows: usize) -> Duration {
+        let (clock, until_next) = match local_time() {
+            Ok((hours, minutes, seconds)) => {
+                let clock = format!("{hours:02}:{minutes:02}");
+                // Seconds can be 60 on a leap second.
+                let until_next = u64::try_from(60 - seconds).unwrap_or(0).max(1);
+                (clock, Duration::from_secs(until_next))
+            }
+            Err(err) => {
+                warn!("error getting the local time: {err:?}");
+                (String::new(), Duration::from_secs(60))
+            }
+        };
+
+        if self.clock != clock || self.windows != windows {
+            self.clock = clock;
+            self.windows = windows;
+            self.buffers.borrow_mut().clear();
+        }
+
+        until_next
+    }
+
+    pub fn render<R: NiriRenderer>(
+        &self,
+        renderer: &mut R,
+        output: &Output,
+    ) -> Option<PrimaryGpuTextureRenderElement> {
+        let scale = output.current_scale().fractional_scale();
+        let output_size = output_size(output);
+
+        let mut buffers = self.buffers.borrow_mut();
+        let buffer = buffers
+            .entry(NotNan::new(scale).unwrap())
+            .or_insert_with(|| match render(scale, &self.clock, self.windows) {
+                Ok(x) => Some(x),
+                Err(err) => {
+                    warn!("error rendering the lock summary: {err:?}");
+                    None
+                }
+            });
+        let buffer = buffer.as_ref()?;
+
+        let size = buffer.logical_size();
+        let buffer = TextureBuffer::from_memory_buffer(renderer.as_gles_renderer(), buffer).ok()?;
+
+        let location = (output_size.to_point() - size.to_point()).downscale(2.);
+        let location = location.to_physical_precise_round(scale).to_logical(scale);
+
+        let elem = TextureRenderElement::from_texture_buffer(
+            buffer,
+            location,
+            1.,
+            None,
+            None,
+            Kind::Unspecified,
+        );
+        Some(PrimaryGpuTextureRenderElement(elem))
+    }
+}
+
+fn render(scale: f64, clock: &str, windows: usize) -> anyhow::Result<MemoryBuffer> {
+    let _span = tracy_client::span!("lock_summary::render");
+
+    let summary = match windows {
+        0 => String::from("Locked"),
+        1 => String::from("1 window locked"),
+        n => format!("{n} windows locked"),
+    };
+
+    let mut clock_font = FontDescription::from_string(CLOCK_FONT);
+    clock_font.set_absolute_size(to_physical_precise_round(scale, clock_font.size()));
+    let mut font = FontDescription::from_string(FONT);
+    font.set_absolute_size(to_physical_precise_round(scale, font.size()));
+
+    let gap: i32 = to_physical_precise_round(scale, GAP);
+
+    let set_up_layouts = |cr: &cairo::Context| {
+        let make_layout = |font: &FontDescription, text: &str| {
+            let layout = pangocairo::functions::create_layout(cr);
+            layout.context().set_round_glyph_positions(false);
+            layout.set_font_description(Some(font));
+            layout.set_alignment(Alignment::Center);
+            layout.set_text(text);
+            layout
+        };
+        (
+            make_layout(&clock_font, clock),
+            make_layout(&font, &summary),
+        )
+    };
+
+    // Render to a dummy surface to determine the size.
+    let surface = ImageSurface::create(cairo::Format::ARgb32, 0, 0)?;
+    let cr = cairo::Context::new(&surface)?;
+    let (clock_layout, summary_layout) = set_up_layouts(&cr);
+    let (clock_width, clock_height) = clock_layout.pixel_size();
+    let (summary_width, summary_height) = summary_layout.pixel_size();
+
+    let width = i32::max(clock_width, summary_width);
+    let height = clock_height + gap + summary_height;
+
+    let surface = ImageSurface::create(cairo::Format::ARgb32, width, height)?;
+    let cr = cairo::Context::new(&surface)?;
+    let (clock_layout, summary_layout) = set_up_layouts(&cr);
+
+    let show_centered = |layout: &Layout, layout_width: i32, y: i32| {
+        cr.move_to(f64::from((width - layout_width) / 2), f64::from(y));
+        pangocairo::functions::show_layout(&cr, layout);
+    };
+
+    cr.set_source_rgb(1., 1., 1.);
+    show_centered(&clock_layout, clock_width, 0);
+    cr.set_source_rgb(0.7, 0.7, 0.7);
+    show_centered(&summary_layout, summary_width, clock_height + gap);
+    drop(cr);
+
+    let data = surface.take_data().unwrap();
+    let buffer = MemoryBuffer::new(
+        data.to_vec(),
+        Fourcc::Argb8888,
+        (width, height),
+        scale,
+        Transform::Normal,
+    );
+
+    Ok(buffer)
+}
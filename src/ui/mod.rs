@@ -1,6 +1,10 @@
 pub mod config_error_notification;
 pub mod exit_confirm_dialog;
 pub mod hotkey_overlay;
+pub mod lock_summary;
 pub mod mru;
 pub mod screen_transition;
 pub mod screenshot_ui;
+pub mod spotlight;
+pub mod window_labels;
+pub mod zoom_lens;
@@ -1,5 +1,5 @@
 use std::cell::RefCell;
-use std::cmp::min;
+use std::cmp::{min, Reverse};
 use std::collections::HashMap;
 use std::mem;
 use std::rc::Rc;
@@ -44,6 +44,8 @@ use crate::utils::{
 use crate::window::mapped::MappedId;
 use crate::window::Mapped;
 
+pub mod persist;
+
 #[cfg(test)]
 mod tests;
 
@@ -74,6 +76,9 @@ const BACKDROP_COLOR: Color32F = Color32F::new(0., 0., 0., 0.8);
 /// Font used to render the window titles.
 const FONT: &str = "sans 14px";
 
+/// Workspace names longer than this many characters get truncated in the window titles.
+const MAX_WORKSPACE_NAME_CHARS: usize = 24;
+
 /// Scopes in the order they are cycled through.
 ///
 /// Count must match one defined in `generate_scope_panels()`.
@@ -93,6 +98,9 @@ pub struct WindowMru {
 
     /// Current filter.
     app_id_filter: Option<String>,
+
+    /// Current search query, `None` when not searching.
+    query: Option<String>,
 }
 
 pub struct WindowMruUi {
@@ -164,6 +172,9 @@ struct Inner {
     /// Scope panel textures.
     scope_panel: RefCell<ScopePanel>,
 
+    /// Search panel texture.
+    search_panel: RefCell<SearchPanel>,
+
     /// Backdrop buffers for each output.
     backdrop_buffers: RefCell<HashMap<Output, SolidColorBuffer>>,
 
@@ -190,6 +201,7 @@ type MruTexture = TextureBuffer<GlesTexture>;
 /// Cached title texture.
 #[derive(Debug, Default)]
 struct TitleTexture {
+    /// Pango markup of the title.
     title: String,
     scale: f64,
     texture: Option<Option<MruTexture>>,
@@ -202,12 +214,20 @@ struct ScopePanel {
     textures: Option<Option<[MruTexture; 3]>>,
 }
 
+/// Cached search panel texture.
+#[derive(Debug, Default)]
+struct SearchPanel {
+    query: String,
+    scale: f64,
+    texture: Option<Option<MruTexture>>,
+}
+
 #[derive(Debug)]
 struct Thumbnail {
     id: MappedId,
 
-    /// Focus timestamp, if any.
-    timestamp: Option<Duration>,
+    /// Key for the recent windows order, see [`Mapped::mru_key()`].
+    mru_key: (Option<Duration>, Option<Reverse<usize>>),
     /// Whether the window is on the current MRU workspace.
     on_current_workspace: bool,
     /// Whether the window is on the current MRU output.
@@ -217,6 +237,14 @@ struct Thumbnail {
     ///
     /// Currently not updated live to avoid having to refilter windows.
     app_id: Option<String>,
+    /// Cached title of the window, used for searching.
+    ///
+    /// Currently not updated live to avoid having to refilter windows.
+    title: Option<String>,
+    /// Name of the workspace of the window, shown before its title.
+    ///
+    /// Only set when the windows are on more than one workspace.
+    workspace: Option<String>,
     /// Cached size of the window.
     size: Size<i32, Logical>,
 
@@ -231,7 +259,9 @@ struct Thumbnail {
 
 impl Thumbnail {
     fn from_mapped(mapped: &Mapped, clock: Clock, config: niri_config::MruPreviews) -> Self {
-        let app_id = with_toplevel_role(mapped.toplevel(), |role| role.app_id.clone());
+        let (app_id, title) = with_toplevel_role(mapped.toplevel(), |role| {
+            (role.app_id.clone(), role.title.clone())
+        });
 
         let background = FocusRing::new(niri_config::FocusRing {
             off: false,
@@ -247,10 +277,12 @@ impl Thumbnail {
 
         Self {
             id: mapped.id(),
-            timestamp: mapped.get_focus_timestamp(),
+            mru_key: mapped.mru_key(),
             on_current_output: false,
             on_current_workspace: false,
             app_id,
+            title,
+            workspace: None,
             size: mapped.size(),
             clock,
             config,
@@ -302,6 +334,13 @@ impl Thumbnail {
         self.size = mapped.size();
     }
 
+    /// Scores the thumbnail against a search query, or returns `None` if it doesn't match.
+    fn search_score(&self, query: &str) -> Option<i32> {
+        let title = self.title.as_deref().and_then(|x| fuzzy_score(query, x));
+        let app_id = self.app_id.as_deref().and_then(|x| fuzzy_score(query, x));
+        title.max(app_id)
+    }
+
     fn preview_size(&self, output_size: Size<f64, Logical>, scale: f64) -> Size<f64, Logical> {
         let max_height = f64::max(1., self.config.max_height);
         let max_scale = f64::max(0.001, self.config.max_scale);
@@ -329,9 +368,10 @@ impl Thumbnail {
         scale: f64,
     ) -> Option<MruTexture> {
         with_toplevel_role(mapped.toplevel(), |role| {
-            role.title
-                .as_ref()
-                .and_then(|title| self.title_texture.borrow_mut().get(renderer, title, scale))
+            let markup = title_markup(self.workspace.as_deref(), role.title.as_deref())?;
+            self.title_texture
+                .borrow_mut()
+                .get(renderer, &markup, scale)
         })
     }
 
@@ -574,6 +614,7 @@ impl WindowMru {
                 current_id: None,
                 scope: MruScope::All,
                 app_id_filter: None,
+                query: None,
             };
         };
 
@@ -584,16 +625,32 @@ impl WindowMru {
             let on_current_output = mon.output() == output;
             let on_current_workspace = on_current_output && mon.active_workspace_idx() == ws_idx;
 
+            // Unnamed workspaces go by their index on the output.
+            let workspace = ws
+                .name()
+                .cloned()
+                .unwrap_or_else(|| (ws_idx + 1).to_string());
+
             for mapped in ws.windows() {
                 let mut thumbnail = Thumbnail::from_mapped(mapped, niri.clock.clone(), config);
                 thumbnail.on_current_output = on_current_output;
                 thumbnail.on_current_workspace = on_current_workspace;
+                thumbnail.workspace = Some(workspace.clone());
                 thumbnails.push(thumbnail);
             }
         }
 
-        thumbnails
-            .sort_by(|Thumbnail { timestamp: t1, .. }, Thumbnail { timestamp: t2, .. }| t2.cmp(t1));
+        // The workspace only tells windows apart when they are on different ones.
+        let workspaces_differ = thumbnails
+            .windows(2)
+            .any(|t| t[0].workspace != t[1].workspace);
+        if !workspaces_differ {
+            for thumbnail in &mut thumbnails {
+                thumbnail.workspace = None;
+            }
+        }
+
+        sort_thumbnails(&mut thumbnails);
 
         let current_id = thumbnails.first().map(|t| t.id);
         Self {
@@ -601,6 +658,7 @@ impl WindowMru {
             current_id,
             scope: MruScope::All,
             app_id_filter: None,
+            query: None,
         }
     }
 
@@ -624,17 +682,29 @@ impl WindowMru {
     }
 
     fn thumbnails(&self) -> impl DoubleEndedIterator<Item = &Thumbnail> {
-        let matches = match_filter(self.scope, self.app_id_filter.as_deref());
+        let matches = match_filter(
+            self.scope,
+            self.app_id_filter.as_deref(),
+            self.query.as_deref(),
+        );
         self.thumbnails.iter().filter(move |t| matches(t))
     }
 
     fn thumbnails_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut Thumbnail> {
-        let matches = match_filter(self.scope, self.app_id_filter.as_deref());
+        let matches = match_filter(
+            self.scope,
+            self.app_id_filter.as_deref(),
+            self.query.as_deref(),
+        );
         self.thumbnails.iter_mut().filter(move |t| matches(t))
     }
 
     fn thumbnails_with_idx(&self) -> impl DoubleEndedIterator<Item = (usize, &Thumbnail)> {
-        let matches = match_filter(self.scope, self.app_id_filter.as_deref());
+        let matches = match_filter(
+            self.scope,
+            self.app_id_filter.as_deref(),
+            self.query.as_deref(),
+        );
         self.thumbnails
             .iter()
             .enumerate()
@@ -786,6 +856,39 @@ impl WindowMru {
         }
     }
 
+    /// Sets the search query, `None` to stop searching.
+    ///
+    /// Selects the best match for a non-empty query, preferring more recent windows on ties.
+    /// Returns the previous query, or `None` if the query was unchanged.
+    pub fn set_query(&mut self, query: Option<String>) -> Option<Option<String>> {
+        if self.query == query {
+            return None;
+        }
+        let old = mem::replace(&mut self.query, query);
+
+        match self.query.as_deref().filter(|query| !query.is_empty()) {
+            Some(query) => {
+                let mut best: Option<(i32, MappedId)> = None;
+                for thumbnail in self.thumbnails() {
+                    let score = thumbnail.search_score(query).unwrap_or_default();
+                    if best.map_or(true, |(best_score, _)| best_score < score) {
+                        best = Some((score, thumbnail.id));
+                    }
+                }
+                self.current_id = best.map(|(_, id)| id);
+            }
+            None => {
+                // The filter got wider or stayed the same, so keep the current thumbnail, or
+                // select the first one if nothing was matching.
+                if self.current_id.is_none() {
+                    self.current_id = self.first_id();
+                }
+            }
+        }
+
+        Some(old)
+    }
+
     fn idx_of(&self, id: MappedId) -> Option<usize> {
         self.thumbnails.iter().position(|t| t.id == id)
     }
@@ -821,7 +924,12 @@ impl WindowMru {
     }
 }
 
-fn matches(scope: MruScope, app_id_filter: Option<&str>, thumbnail: &Thumbnail) -> bool {
+fn matches(
+    scope: MruScope,
+    app_id_filter: Option<&str>,
+    query: Option<&str>,
+    thumbnail: &Thumbnail,
+) -> bool {
     let x = match scope {
         MruScope::All => true,
         MruScope::Output => thumbnail.on_current_output,
@@ -832,14 +940,69 @@ fn matches(scope: MruScope, app_id_filter: Option<&str>, thumbnail: &Thumbnail)
     }
 
     if let Some(app_id) = app_id_filter {
-        thumbnail.app_id.as_deref() == Some(app_id)
+        if thumbnail.app_id.as_deref() != Some(app_id) {
+            return false;
+        }
+    }
+
+    if let Some(query) = query {
+        thumbnail.search_score(query).is_some()
     } else {
         true
     }
 }
 
-fn match_filter(scope: MruScope, app_id_filter: Option<&str>) -> impl Fn(&Thumbnail) -> bool + '_ {
-    move |thumbnail| matches(scope, app_id_filter, thumbnail)
+/// Sorts thumbnails in the recent windows order, most recent first.
+fn sort_thumbnails(thumbnails: &mut [Thumbnail]) {
+    thumbnails.sort_by(|a, b| b.mru_key.cmp(&a.mru_key));
+}
+
+fn match_filter<'a>(
+    scope: MruScope,
+    app_id_filter: Option<&'a str>,
+    query: Option<&'a str>,
+) -> impl Fn(&Thumbnail) -> bool + 'a {
+    move |thumbnail| matches(scope, app_id_filter, query, thumbnail)
+}
+
+/// Scores how well `text` matches the search `query`, or returns `None` if it doesn't match.
+///
+/// The query matches when all of its characters appear in `text` in the same order, ignoring case
+/// and whitespace in the query. Runs of consecutive characters and characters at the start of a
+/// word score higher.
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    let mut text = text.chars().flat_map(char::to_lowercase);
+    let mut prev: Option<char> = None;
+    let mut prev_matched = false;
+    let mut score = 0;
+
+    for q in query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| !c.is_whitespace())
+    {
+        loop {
+            let c = text.next()?;
+            let word_start = prev.map_or(true, |prev| !prev.is_alphanumeric());
+            prev = Some(c);
+
+            if c != q {
+                prev_matched = false;
+                continue;
+            }
+
+            score += 1;
+            if prev_matched {
+                score += 4;
+            } else if word_start {
+                score += 3;
+            }
+            prev_matched = true;
+            break;
+        }
+    }
+
+    Some(score)
 }
 
 impl ViewPos {
@@ -939,6 +1102,7 @@ impl WindowMruUi {
             config: self.config.clone(),
             output,
             scope_panel: Default::default(),
+            search_panel: Default::default(),
             backdrop_buffers: Default::default(),
             offscreen: OffscreenBuffer::default(),
         };
@@ -1022,6 +1186,55 @@ impl WindowMruUi {
         self.set_scope(scope);
     }
 
+    pub fn is_searching(&self) -> bool {
+        match &self.state {
+            UiState::Open(inner) => inner.wmru.query.is_some(),
+            _ => false,
+        }
+    }
+
+    pub fn start_search(&mut self) {
+        let UiState::Open(inner) = &mut self.state else {
+            return;
+        };
+        if inner.wmru.query.is_none() {
+            inner.set_query(Some(String::new()));
+        }
+    }
+
+    pub fn search_push(&mut self, c: char) {
+        let UiState::Open(inner) = &mut self.state else {
+            return;
+        };
+        let Some(query) = &inner.wmru.query else {
+            return;
+        };
+
+        let mut query = query.clone();
+        query.push(c);
+        inner.freeze_view = false;
+        inner.set_query(Some(query));
+    }
+
+    /// Erases the last character of the search query, or stops searching if it is empty.
+    pub fn search_backspace(&mut self) {
+        let UiState::Open(inner) = &mut self.state else {
+            return;
+        };
+        let Some(query) = &inner.wmru.query else {
+            return;
+        };
+
+        let mut query = query.clone();
+        let query = if query.pop().is_some() {
+            Some(query)
+        } else {
+            None
+        };
+        inner.freeze_view = false;
+        inner.set_query(query);
+    }
+
     pub fn pointer_motion(&mut self, pos_within_output: Point<f64, Logical>) -> Option<MappedId> {
         let UiState::Open(inner) = &mut self.state else {
             return None;
@@ -1405,7 +1618,7 @@ impl Inner {
     fn set_scope(&mut self, scope: MruScope) {
         let was_empty = self.wmru.current_id.is_none();
         if let Some(old_scope) = self.wmru.set_scope(scope) {
-            self.animate_scope_filter_change(was_empty, old_scope, None);
+            self.animate_scope_filter_change(was_empty, old_scope, None, None);
         }
     }
 
@@ -1413,7 +1626,15 @@ impl Inner {
         let was_empty = self.wmru.current_id.is_none();
         if let Some(old_filter) = self.wmru.set_filter(filter) {
             let old_filter = Some(old_filter.as_deref());
-            self.animate_scope_filter_change(was_empty, self.wmru.scope, old_filter);
+            self.animate_scope_filter_change(was_empty, self.wmru.scope, old_filter, None);
+        }
+    }
+
+    fn set_query(&mut self, query: Option<String>) {
+        let was_empty = self.wmru.current_id.is_none();
+        if let Some(old_query) = self.wmru.set_query(query) {
+            let old_query = Some(old_query.as_deref());
+            self.animate_scope_filter_change(was_empty, self.wmru.scope, None, old_query);
         }
     }
 
@@ -1422,6 +1643,7 @@ impl Inner {
         was_empty: bool,
         old_scope: MruScope,
         old_filter: Option<Option<&str>>,
+        old_query: Option<Option<&str>>,
     ) {
         let Some(id) = self.wmru.current_id else {
             // If there's no current_id then the new filter caused all windows to disappear, so
@@ -1433,8 +1655,13 @@ impl Inner {
         // Animate opening for newly appeared thumbnails.
         let config = self.config.borrow().animations.window_open.anim;
         let old_filter = old_filter.unwrap_or(self.wmru.app_id_filter.as_deref());
-        let matches_old = match_filter(old_scope, old_filter);
-        let matches_new = match_filter(self.wmru.scope, self.wmru.app_id_filter.as_deref());
+        let old_query = old_query.unwrap_or(self.wmru.query.as_deref());
+        let matches_old = match_filter(old_scope, old_filter, old_query);
+        let matches_new = match_filter(
+            self.wmru.scope,
+            self.wmru.app_id_filter.as_deref(),
+            self.wmru.query.as_deref(),
+        );
         for thumbnail in &mut self.wmru.thumbnails {
             if matches_new(thumbnail) && !matches_old(thumbnail) {
                 thumbnail.animate_open_with_config(config);
@@ -1576,6 +1803,30 @@ impl Inner {
         });
         let panel = panel.into_iter();
 
+        let search_panel = self.wmru.query.as_deref().and_then(|query| {
+            let texture =
+                self.search_panel
+                    .borrow_mut()
+                    .get(renderer.as_gles_renderer(), scale, query)?;
+            let padding = round_logical_in_physical(scale, f64::from(PANEL_PADDING));
+
+            let size = texture.logical_size();
+            let location = Point::new(
+                (output_size.w - size.w) / 2.,
+                output_size.h - size.h - padding * 2.,
+            );
+            let elem = PrimaryGpuTextureRenderElement(TextureRenderElement::from_texture_buffer(
+                texture,
+                location,
+                1.,
+                None,
+                None,
+                Kind::Unspecified,
+            ));
+            Some(WindowMruUiRenderElement::TextureElement(elem))
+        });
+        let panel = panel.chain(search_panel);
+
         let current_id = self.wmru.current_id;
 
         let bob_y = baba_is_float_offset(self.clock.now(), output_size.h);
@@ -1658,9 +1909,32 @@ impl TitleTexture {
     }
 }
 
+/// Returns the Pango markup for a window title, prefixed with its workspace, if any.
+fn title_markup(workspace: Option<&str>, title: Option<&str>) -> Option<String> {
+    let title = title.map(|title| pango::glib::markup_escape_text(title).to_string());
+    let Some(workspace) = workspace else {
+        return title;
+    };
+
+    let workspace = if workspace.chars().count() > MAX_WORKSPACE_NAME_CHARS {
+        let mut truncated = String::from_iter(workspace.chars().take(MAX_WORKSPACE_NAME_CHARS - 1));
+        truncated.push('…');
+        truncated
+    } else {
+        workspace.to_owned()
+    };
+    let workspace = pango::glib::markup_escape_text(&workspace);
+    let workspace = format!("<span fgcolor='#999999'>{workspace}</span>");
+
+    Some(match title {
+        Some(title) => format!("{workspace}  {title}"),
+        None => workspace,
+    })
+}
+
 fn generate_title_texture(
     renderer: &mut GlesRenderer,
-    title: &str,
+    markup: &str,
     scale: f64,
 ) -> anyhow::Result<MruTexture> {
     let _span = tracy_client::span!("mru::generate_title_texture");
@@ -1676,7 +1950,7 @@ fn generate_title_texture(
     // No use rendering it as multiple lines.
     layout.set_single_paragraph_mode(true);
     layout.set_font_description(Some(&font));
-    layout.set_text(title);
+    layout.set_markup(markup);
 
     let (width, height) = layout.pixel_size();
     ensure!(width > 0 && height > 0);
@@ -1725,6 +1999,24 @@ impl ScopePanel {
     }
 }
 
+impl SearchPanel {
+    fn get(&mut self, renderer: &mut GlesRenderer, scale: f64, query: &str) -> Option<MruTexture> {
+        if self.query != query || self.scale != scale {
+            self.texture = None;
+            self.query = query.to_owned();
+            self.scale = scale;
+        }
+
+        self.texture
+            .get_or_insert_with(|| {
+                let query = pango::glib::markup_escape_text(query);
+                let text = format!("<span fgcolor='#999999'>Search:</span> {query}_");
+                render_panel(renderer, scale, &text).ok()
+            })
+            .clone()
+    }
+}
+
 fn generate_scope_panels(
     renderer: &mut GlesRenderer,
     scale: f64,
@@ -1855,6 +2147,7 @@ fn make_preset_opened_binds() -> Vec<Bind> {
     push(Keysym::o, Action::MruSetScope(MruScope::Output));
     push(Keysym::w, Action::MruSetScope(MruScope::Workspace));
     push(Keysym::s, Action::MruCycleScope);
+    push(Keysym::slash, Action::MruStartSearch);
 
     // Leave these in since they are the most expected and generally uncontroversial keys, so that
     // they work even if these actions are absent from the normal binds.
@@ -0,0 +1,133 @@
+//! Saving and restoring the recent windows order across niri restarts.
+
+use std::fs;
+use std::path::PathBuf;
+
+use anyhow::Context as _;
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+
+const FILE_NAME: &str = "recent-windows.json";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SavedOrder {
+    /// App IDs of windows in the recent windows order, most recent first.
+    ///
+    /// Every window contributes its own entry, so an app ID can appear several times.
+    app_ids: Vec<String>,
+}
+
+/// Recent windows order loaded at startup, waiting for the windows to open again.
+#[derive(Debug)]
+pub struct MruRestore {
+    /// Saved entries, most recent first; `None` for entries already claimed by a window.
+    entries: Vec<Option<String>>,
+}
+
+impl MruRestore {
+    pub fn load() -> Option<Self> {
+        let path = path()?;
+        let contents = match fs::read_to_string(&path) {
+            Ok(x) => x,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(err) => {
+                warn!("error reading {path:?}: {err:?}");
+                return None;
+            }
+        };
+
+        let saved: SavedOrder = match serde_json::from_str(&contents) {
+            Ok(x) => x,
+            Err(err) => {
+                warn!("error parsing {path:?}: {err:?}");
+                return None;
+            }
+        };
+
+        debug!("loaded {} recent windows entries", saved.app_ids.len());
+        Some(Self::new(saved.app_ids))
+    }
+
+    fn new(app_ids: Vec<String>) -> Self {
+        Self {
+            entries: app_ids.into_iter().map(Some).collect(),
+        }
+    }
+
+    /// Claims the most recent unclaimed entry for a newly opened window with this app ID.
+    ///
+    /// Returns the rank of the entry in the saved order, lower is more recent.
+    pub fn claim(&mut self, app_id: &str) -> Option<usize> {
+        let (rank, entry) = self
+            .entries
+            .iter_mut()
+            .enumerate()
+            .find(|(_, entry)| entry.as_deref() == Some(app_id))?;
+        *entry = None;
+        Some(rank)
+    }
+
+    /// Returns the entries not claimed by any window yet, most recent first.
+    pub fn unclaimed(&self) -> impl Iterator<Item = &str> + '_ {
+        self.entries.iter().filter_map(|entry| entry.as_deref())
+    }
+}
+
+/// Saves app IDs in the recent windows order, most recent first.
+pub fn save(app_ids: Vec<String>) -> anyhow::Result<()> {
+    let path = path().context("error getting the state directory")?;
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).with_context(|| format!("error creating {parent:?}"))?;
+    }
+
+    let contents = serde_json::to_string(&SavedOrder { app_ids })?;
+
+    // Write to a temporary file first so that a crash mid-write doesn't lose the old order.
+    let tmp = path.with_extension("json.tmp");
+    fs::write(&tmp, contents).with_context(|| format!("error writing {tmp:?}"))?;
+    fs::rename(&tmp, &path).with_context(|| format!("error renaming {tmp:?} to {path:?}"))?;
+
+    Ok(())
+}
+
+fn path() -> Option<PathBuf> {
+    let dirs = ProjectDirs::from("", "", "niri")?;
+    Some(dirs.state_dir()?.join(FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn claim_keeps_saved_order() {
+        let mut restore = MruRestore::new(vec![
+            String::from("firefox"),
+            String::from("Alacritty"),
+            String::from("firefox"),
+        ]);
+
+        let newest_firefox = restore.claim("firefox").unwrap();
+        let alacritty = restore.claim("Alacritty").unwrap();
+        let oldest_firefox = restore.claim("firefox").unwrap();
+
+        assert!(newest_firefox < alacritty);
+        assert!(alacritty < oldest_firefox);
+        assert_eq!(restore.claim("firefox"), None);
+        assert_eq!(restore.claim("foot"), None);
+    }
+
+    #[test]
+    fn unclaimed_keeps_saved_order() {
+        let mut restore = MruRestore::new(vec![
+            String::from("firefox"),
+            String::from("Alacritty"),
+            String::from("foot"),
+        ]);
+
+        restore.claim("Alacritty");
+
+        let unclaimed = Vec::from_iter(restore.unclaimed());
+        assert_eq!(unclaimed, ["firefox", "foot"]);
+    }
+}
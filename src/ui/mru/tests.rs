@@ -6,10 +6,12 @@ use super::*;
 fn create_thumbnail() -> Thumbnail {
     Thumbnail {
         id: MappedId::next(),
-        timestamp: None,
+        mru_key: (None, None),
         on_current_output: false,
         on_current_workspace: false,
         app_id: None,
+        title: None,
+        workspace: None,
         size: Size::new(100, 100),
         clock: Clock::with_time(Duration::ZERO),
         config: niri_config::MruPreviews::default(),
@@ -32,11 +34,79 @@ fn remove_last_window_out_of_two() {
         current_id,
         scope: MruScope::All,
         app_id_filter: None,
+        query: None,
     };
 
     check_ops(&mut mru, &ops);
 }
 
+#[test]
+fn fuzzy_score_matches_in_order() {
+    assert_eq!(fuzzy_score("", "Terminal"), Some(0));
+    assert!(fuzzy_score("TERM", "terminal").is_some());
+    assert!(fuzzy_score("t m", "terminal").is_some());
+    assert_eq!(fuzzy_score("mt", "terminal"), None);
+    assert_eq!(fuzzy_score("terminals", "terminal"), None);
+}
+
+#[test]
+fn fuzzy_score_prefers_runs_and_word_starts() {
+    assert_eq!(fuzzy_score("term", "Terminal"), Some(19));
+    assert_eq!(fuzzy_score("term", "the rem"), Some(10));
+    assert!(fuzzy_score("fox", "Firefox") < fuzzy_score("fox", "fox news"));
+}
+
+#[test]
+fn title_markup_shows_workspace() {
+    assert_eq!(title_markup(None, None), None);
+    assert_eq!(title_markup(None, Some("a & b")).unwrap(), "a &amp; b");
+    assert_eq!(
+        title_markup(Some("dev"), Some("Terminal")).unwrap(),
+        "<span fgcolor='#999999'>dev</span>  Terminal"
+    );
+    assert_eq!(
+        title_markup(Some("ops"), None).unwrap(),
+        "<span fgcolor='#999999'>ops</span>"
+    );
+    assert_eq!(
+        title_markup(Some("a workspace with a really long name"), None).unwrap(),
+        "<span fgcolor='#999999'>a workspace with a real…</span>"
+    );
+}
+
+#[test]
+fn query_selects_best_match() {
+    let mut thumbnails = vec![create_thumbnail(), create_thumbnail(), create_thumbnail()];
+    thumbnails[0].title = Some(String::from("the rem"));
+    thumbnails[1].title = Some(String::from("Terminal"));
+    thumbnails[2].title = Some(String::from("Files"));
+    let ids = thumbnails.iter().map(|t| t.id).collect::<Vec<_>>();
+
+    let mut mru = WindowMru {
+        thumbnails,
+        current_id: Some(ids[2]),
+        scope: MruScope::All,
+        app_id_filter: None,
+        query: None,
+    };
+
+    // Starting a search keeps the selection.
+    mru.set_query(Some(String::new()));
+    assert_eq!(mru.current_id, Some(ids[2]));
+
+    mru.set_query(Some(String::from("term")));
+    assert_eq!(mru.current_id, Some(ids[1]));
+    assert_eq!(mru.thumbnails().count(), 2);
+
+    mru.set_query(Some(String::from("termx")));
+    assert_eq!(mru.current_id, None);
+    mru.verify_invariants();
+
+    mru.set_query(None);
+    assert_eq!(mru.current_id, Some(ids[0]));
+    assert_eq!(mru.thumbnails().count(), 3);
+}
+
 fn arbitrary_scope() -> impl Strategy<Value = MruScope> {
     prop_oneof![
         Just(MruScope::All),
@@ -54,18 +124,39 @@ fn arbitrary_app_id() -> impl Strategy<Value = Option<String>> {
         .prop_map(|id| id.map(|id| format!("app-{id}")))
 }
 
+fn arbitrary_title() -> impl Strategy<Value = Option<String>> {
+    prop_oneof![
+        Just(None),
+        Just(Some(String::from("Terminal"))),
+        Just(Some(String::from("the rem"))),
+    ]
+}
+
+fn arbitrary_query() -> impl Strategy<Value = Option<String>> {
+    prop_oneof![
+        Just(None),
+        Just(Some(String::new())),
+        Just(Some(String::from("te"))),
+        Just(Some(String::from("app-1"))),
+        Just(Some(String::from("xyz"))),
+    ]
+}
+
 prop_compose! {
     fn arbitrary_thumbnail()(
         timestamp: Option<Duration>,
+        restored_rank in proptest::option::of(0..5usize),
         on_current_output: bool,
         on_current_workspace: bool,
         app_id in arbitrary_app_id(),
+        title in arbitrary_title(),
     ) -> Thumbnail {
         let mut thumbnail = create_thumbnail();
-        thumbnail.timestamp = timestamp;
+        thumbnail.mru_key = (timestamp, restored_rank.map(Reverse));
         thumbnail.on_current_workspace = on_current_workspace;
         thumbnail.on_current_output = on_current_output;
         thumbnail.app_id = app_id;
+        thumbnail.title = title;
         thumbnail
     }
 }
@@ -80,6 +171,7 @@ prop_compose! {
             current_id,
             scope: MruScope::All,
             app_id_filter: None,
+            query: None,
         }
     }
 }
@@ -92,6 +184,7 @@ enum Op {
     Last,
     SetScope(#[proptest(strategy = "arbitrary_scope()")] MruScope),
     SetFilter(#[proptest(strategy = "arbitrary_filter()")] MruFilter),
+    SetQuery(#[proptest(strategy = "arbitrary_query()")] Option<String>),
     Remove(#[proptest(strategy = "1..10usize")] usize),
 }
 
@@ -108,6 +201,9 @@ impl Op {
             Op::SetFilter(filter) => {
                 mru.set_filter(*filter);
             }
+            Op::SetQuery(query) => {
+                mru.set_query(query.clone());
+            }
             Op::Remove(idx) => {
                 if *idx < mru.thumbnails.len() {
                     mru.remove_by_idx(*idx);
@@ -132,4 +228,25 @@ proptest! {
     ) {
         check_ops(&mut mru, &ops);
     }
+
+    #[test]
+    fn sort_puts_restored_windows_after_focused(
+        mut thumbnails in proptest::collection::vec(arbitrary_thumbnail(), 1..10),
+    ) {
+        sort_thumbnails(&mut thumbnails);
+
+        for pair in thumbnails.windows(2) {
+            let ((t1, r1), (t2, r2)) = (pair[0].mru_key, pair[1].mru_key);
+            match (t1, t2) {
+                (Some(t1), Some(t2)) => prop_assert!(t1 >= t2),
+                (None, Some(_)) => prop_assert!(false, "focused windows must come first"),
+                (Some(_), None) => (),
+                (None, None) => match (r1, r2) {
+                    (Some(r1), Some(r2)) => prop_assert!(r1.0 <= r2.0),
+                    (None, Some(_)) => prop_assert!(false, "restored windows must come next"),
+                    _ => (),
+                },
+            }
+        }
+    }
 }
@@ -19,7 +19,7 @@ use smithay::backend::renderer::gles::{GlesRenderer, GlesTexture};
 use smithay::backend::renderer::{ExportMem, Texture as _};
 use smithay::input::keyboard::{Keysym, ModifiersState};
 use smithay::output::{Output, WeakOutput};
-use smithay::utils::{Buffer, Physical, Point, Rectangle, Scale, Size, Transform};
+use smithay::utils::{Buffer, Logical, Physical, Point, Rectangle, Scale, Size, Transform};
 
 use crate::animation::{Animation, Clock};
 use crate::layout::floating::DIRECTIONAL_MOVE_PX;
@@ -38,10 +38,12 @@ const FONT: &str = "sans 14px";
 const BORDER: i32 = 4;
 const TEXT_HIDE_P: &str =
     "Press <span face='mono' bgcolor='#2C2C2C'> Space </span> to save the screenshot.\n\
-     Press <span face='mono' bgcolor='#2C2C2C'> P </span> to hide the pointer.";
+     Press <span face='mono' bgcolor='#2C2C2C'> P </span> to hide the pointer.\n\
+     Press <span face='mono' bgcolor='#2C2C2C'> W </span> to select the focused window.";
 const TEXT_SHOW_P: &str =
     "Press <span face='mono' bgcolor='#2C2C2C'> Space </span> to save the screenshot.\n\
-     Press <span face='mono' bgcolor='#2C2C2C'> P </span> to show the pointer.";
+     Press <span face='mono' bgcolor='#2C2C2C'> P </span> to show the pointer.\n\
+     Press <span face='mono' bgcolor='#2C2C2C'> W </span> to select the focused window.";
 
 // Ideally the screenshot UI should support cross-output selections. However, that poses some
 // technical challenges when the outputs have different scales and such. So, this implementation
@@ -460,6 +462,40 @@ impl ScreenshotUi {
         self.update_buffers();
     }
 
+    /// Sets the selection to a rectangle in output-local logical coordinates.
+    ///
+    /// The rectangle is clamped to the output.
+    pub fn select_rect(&mut self, output: &Output, rect: Rectangle<f64, Logical>) {
+        let Self::Open {
+            selection,
+            output_data,
+            ..
+        } = self
+        else {
+            return;
+        };
+
+        let Some(data) = output_data.get(output) else {
+            return;
+        };
+
+        let rect = rect.to_physical_precise_round(data.scale);
+        let Some(rect) = rect.intersection(Rectangle::from_size(data.size)) else {
+            return;
+        };
+        if rect.is_empty() {
+            return;
+        }
+
+        *selection = (
+            output.clone(),
+            rect.loc,
+            rect.loc + rect.size - Size::from((1, 1)),
+        );
+
+        self.update_buffers();
+    }
+
     pub fn set_width(&mut self, change: SizeChange) {
         let Self::Open {
             selection: (output, a, b),
@@ -1071,6 +1107,10 @@ fn action(raw: Keysym, mods: ModifiersState) -> Option<Action> {
         return Some(Action::ScreenshotTogglePointer);
     }
 
+    if !mods.ctrl && raw == Keysym::w {
+        return Some(Action::ScreenshotSelectFocusedWindow);
+    }
+
     None
 }
 
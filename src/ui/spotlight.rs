@@ -0,0 +1,198 @@
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::iter::zip;
+use std::rc::Rc;
+
+use arrayvec::ArrayVec;
+use niri_config::Config;
+use smithay::backend::renderer::element::Kind;
+use smithay::output::Output;
+use smithay::utils::{Logical, Point, Rectangle, Size};
+
+use crate::animation::{Animation, Clock};
+use crate::render_helpers::solid_color::{SolidColorBuffer, SolidColorRenderElement};
+use crate::utils::output_size;
+use crate::window::mapped::MappedId;
+
+const COLOR: [f32; 4] = [0., 0., 0., 0.7];
+
+/// Overlay that darkens everything except the focused window.
+pub struct Spotlight {
+    is_open: bool,
+    hole: Option<Hole>,
+    /// Buffers for the parts above, below, left and right of the hole on each output.
+    buffers: RefCell<HashMap<Output, [SolidColorBuffer; 4]>>,
+    clock: Clock,
+    config: Rc<RefCell<Config>>,
+}
+
+/// Part of an output left uncovered by the spotlight.
+struct Hole {
+    output: Output,
+    /// Window that the hole is around.
+    window: MappedId,
+    from: Rectangle<f64, Logical>,
+    to: Rectangle<f64, Logical>,
+    anim: Option<Animation>,
+}
+
+impl Spotlight {
+    pub fn new(clock: Clock, config: Rc<RefCell<Config>>) -> Self {
+        Self {
+            is_open: false,
+            hole: None,
+            buffers: RefCell::new(HashMap::new()),
+            clock,
+            config,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    pub fn toggle(&mut self) {
+        self.is_open = !self.is_open;
+        self.hole = None;
+        self.buffers.borrow_mut().clear();
+    }
+
+    /// Updates the window to leave uncovered, along with its output and rectangle within it.
+    ///
+    /// When focus moves to another window on the same output, the hole animates to it. While
+    /// the same window moves or resizes, the hole follows it right away.
+    pub fn update(&mut self, target: Option<(Output, MappedId, Rectangle<f64, Logical>)>) {
+        if !self.is_open {
+            return;
+        }
+
+        let Some((output, window, rect)) = target else {
+            self.hole = None;
+            return;
+        };
+
+        match &mut self.hole {
+            Some(hole) if hole.output == output => {
+                if hole.window != window {
+                    hole.window = window;
+
+                    if hole.to != rect {
+                        let config = self.config.borrow().animations.window_movement.0;
+                        let anim = Animation::new(self.clock.clone(), 0., 1., 0., config);
+                        hole.from = hole.current();
+                        hole.anim = Some(anim);
+                    }
+                }
+
+                // Retargets the running animation, if any.
+                hole.to = rect;
+            }
+            _ => {
+                self.hole = Some(Hole {
+                    output,
+                    window,
+                    from: rect,
+                    to: rect,
+                    anim: None,
+                });
+            }
+        }
+    }
+
+    pub fn advance_animations(&mut self) {
+        if let Some(hole) = &mut self.hole {
+            if hole.anim.as_ref().is_some_and(|anim| anim.is_done()) {
+                hole.anim = None;
+            }
+        }
+    }
+
+    pub fn are_animations_ongoing(&self) -> bool {
+        self.hole.as_ref().is_some_and(|hole| hole.anim.is_some())
+    }
+
+    pub fn render(&self, output: &Output) -> ArrayVec<SolidColorRenderElement, 4> {
+        let mut rv = ArrayVec::new();
+
+        if !self.is_open {
+            return rv;
+        }
+
+        let scale = output.current_scale().fractional_scale();
+        let output_size = output_size(output);
+        let output_rect = Rectangle::from_size(output_size);
+
+        let hole = self
+            .hole
+            .as_ref()
+            .filter(|hole| hole.output == *output)
+            .and_then(|hole| hole.current().intersection(output_rect))
+            .map(|hole| hole.to_physical_precise_round(scale).to_logical(scale));
+
+        let rects = match hole {
+            None => [
+                output_rect,
+                Rectangle::default(),
+                Rectangle::default(),
+                Rectangle::default(),
+            ],
+            Some(hole) => {
+                let top = hole.loc.y;
+                let bottom = hole.loc.y + hole.size.h;
+                let left = hole.loc.x;
+                let right = hole.loc.x + hole.size.w;
+                let (width, height) = (output_size.w, output_size.h);
+                [
+                    Rectangle::new(Point::from((0., 0.)), Size::from((width, top))),
+                    Rectangle::new(
+                        Point::from((0., bottom)),
+                        Size::from((width, height - bottom)),
+                    ),
+                    Rectangle::new(Point::from((0., top)), Size::from((left, hole.size.h))),
+                    Rectangle::new(
+                        Point::from((right, top)),
+                        Size::from((width - right, hole.size.h)),
+                    ),
+                ]
+            }
+        };
+
+        let mut buffers = self.buffers.borrow_mut();
+        let buffers = buffers.entry(output.clone()).or_default();
+
+        for (buffer, rect) in zip(buffers, rects) {
+            if rect.is_empty() {
+                continue;
+            }
+
+            buffer.resize(rect.size);
+            buffer.set_color(COLOR);
+            let elem =
+                SolidColorRenderElement::from_buffer(buffer, rect.loc, 1., Kind::Unspecified);
+            rv.push(elem);
+        }
+
+        rv
+    }
+}
+
+impl Hole {
+    fn current(&self) -> Rectangle<f64, Logical> {
+        let Some(anim) = &self.anim else {
+            return self.to;
+        };
+
+        let t = anim.value();
+        let lerp = |from: f64, to: f64| from + (to - from) * t;
+        Rectangle::new(
+            Point::from((
+                lerp(self.from.loc.x, self.to.loc.x),
+                lerp(self.from.loc.y, self.to.loc.y),
+            )),
+            Size::from((
+                lerp(self.from.size.w, self.to.size.w).max(0.),
+                lerp(self.from.size.h, self.to.size.h).max(0.),
+            )),
+        )
+    }
+}
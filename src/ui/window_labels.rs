@@ -0,0 +1,377 @@
+use std::cell::RefCell;
+
+use pango::FontDescription;
+use pangocairo::cairo::{self, ImageSurface};
+use smithay::backend::allocator::Fourcc;
+use smithay::backend::renderer::element::Kind;
+use smithay::backend::renderer::gles::{GlesRenderer, GlesTexture};
+use smithay::output::Output;
+use smithay::utils::{Logical, Rectangle, Transform};
+
+use crate::layout::{Layout, LayoutElement as _};
+use crate::render_helpers::primary_gpu_texture::PrimaryGpuTextureRenderElement;
+use crate::render_helpers::renderer::NiriRenderer;
+use crate::render_helpers::texture::{TextureBuffer, TextureRenderElement};
+use crate::utils::{output_size, to_physical_precise_round};
+use crate::window::mapped::MappedId;
+use crate::window::Mapped;
+
+/// Characters that labels are made of, in the order they are handed out.
+const ALPHABET: [char; 9] = ['a', 's', 'd', 'f', 'g', 'h', 'j', 'k', 'l'];
+
+const PADDING: i32 = 8;
+const BORDER: i32 = 2;
+const FONT: &str = "mono bold 20px";
+
+/// Overlay that labels every visible window so that it can be focused by typing its label.
+pub struct WindowLabelUi {
+    state: State,
+}
+
+enum State {
+    Closed,
+    Open {
+        labels: Vec<Label>,
+        /// Label characters typed so far.
+        typed: String,
+    },
+}
+
+struct Label {
+    id: MappedId,
+    text: String,
+    texture: RefCell<LabelTexture>,
+}
+
+/// Cached label texture.
+#[derive(Debug, Default)]
+struct LabelTexture {
+    typed_len: usize,
+    scale: f64,
+    texture: Option<Option<TextureBuffer<GlesTexture>>>,
+}
+
+impl WindowLabelUi {
+    pub fn new() -> Self {
+        Self {
+            state: State::Closed,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        matches!(self.state, State::Open { .. })
+    }
+
+    /// Opens the UI, labeling every window currently visible on any output.
+    ///
+    /// Returns `false` if there was nothing to label.
+    pub fn open(&mut self, layout: &Layout<Mapped>) -> bool {
+        let ids = Vec::from_iter(
+            layout
+                .outputs()
+                .flat_map(|output| visible_windows(layout, output))
+                .map(|(mapped, _)| mapped.id()),
+        );
+        if ids.is_empty() {
+            return false;
+        }
+
+        let labels = generate_labels(ids.len());
+        let labels = ids
+            .into_iter()
+            .zip(labels)
+            .map(|(id, text)| Label {
+                id,
+                text,
+                texture: Default::default(),
+            })
+            .collect();
+
+        self.state = State::Open {
+            labels,
+            typed: String::new(),
+        };
+        true
+    }
+
+    /// Returns `true` if the UI was open.
+    pub fn close(&mut self) -> bool {
+        if !self.is_open() {
+            return false;
+        }
+
+        self.state = State::Closed;
+        true
+    }
+
+    /// Handles a typed character.
+    ///
+    /// Once a label is typed out in full, closes the UI and returns the id of its window.
+    /// Characters that don't continue any label are ignored.
+    pub fn type_char(&mut self, c: char) -> Option<MappedId> {
+        let State::Open { labels, typed } = &mut self.state else {
+            return None;
+        };
+
+        typed.push(c.to_ascii_lowercase());
+
+        if let Some(label) = labels.iter().find(|label| label.text == *typed) {
+            let id = label.id;
+            self.state = State::Closed;
+            return Some(id);
+        }
+
+        if !labels
+            .iter()
+            .any(|label| label.text.starts_with(typed.as_str()))
+        {
+            typed.pop();
+        }
+
+        None
+    }
+
+    pub fn backspace(&mut self) {
+        if let State::Open { typed, .. } = &mut self.state {
+            typed.pop();
+        }
+    }
+
+    pub fn render<R: NiriRenderer>(
+        &self,
+        renderer: &mut R,
+        layout: &Layout<Mapped>,
+        output: &Output,
+    ) -> Vec<PrimaryGpuTextureRenderElement> {
+        let mut rv = Vec::new();
+
+        let State::Open { labels, typed } = &self.state else {
+            return rv;
+        };
+
+        let scale = output.current_scale().fractional_scale();
+
+        for (mapped, geo) in visible_windows(layout, output) {
+            let Some(label) = labels.iter().find(|label| label.id == mapped.id()) else {
+                // Mapped after the UI was opened.
+                continue;
+            };
+
+            // Hide labels that no longer match what was typed.
+            if !label.text.starts_with(typed.as_str()) {
+                continue;
+            }
+
+            let mut cache = label.texture.borrow_mut();
+            let Some(texture) =
+                cache.get(renderer.as_gles_renderer(), &label.text, typed.len(), scale)
+            else {
+                continue;
+            };
+
+            let size = texture.logical_size();
+            let loc = geo.loc + (geo.size.to_point() - size.to_point()).downscale(2.);
+            let loc = loc.to_physical_precise_round(scale).to_logical(scale);
+
+            let elem = TextureRenderElement::from_texture_buffer(
+                texture,
+                loc,
+                1.,
+                None,
+                None,
+                Kind::Unspecified,
+            );
+            rv.push(PrimaryGpuTextureRenderElement(elem));
+        }
+
+        rv
+    }
+}
+
+/// Returns windows visible on the output, along with their geometry within the output.
+fn visible_windows<'a>(
+    layout: &'a Layout<Mapped>,
+    output: &Output,
+) -> impl Iterator<Item = (&'a Mapped, Rectangle<f64, Logical>)> + 'a {
+    let output_geo = Rectangle::from_size(output_size(output));
+
+    let mon = layout.monitor_for_output(output);
+    let zoom = mon.map_or(1., |mon| mon.overview_zoom());
+
+    mon.into_iter()
+        .flat_map(|mon| mon.workspaces_with_render_geo())
+        .flat_map(move |(ws, ws_geo)| {
+            ws.tiles_with_render_positions()
+                .filter(|(_, _, visible)| *visible)
+                .map(move |(tile, tile_pos, _)| {
+                    let loc = ws_geo.loc + tile_pos.upscale(zoom);
+                    let size = tile.tile_size().upscale(zoom);
+                    (tile.window(), Rectangle::new(loc, size))
+                })
+        })
+        .filter(move |(_, geo)| geo.intersection(output_geo).is_some())
+}
+
+/// Generates `count` distinct labels of the same, smallest possible, length.
+fn generate_labels(count: usize) -> Vec<String> {
+    let base = ALPHABET.len();
+
+    let mut len = 1;
+    let mut capacity = base;
+    while capacity < count {
+        len += 1;
+        capacity *= base;
+    }
+
+    (0..count)
+        .map(|mut idx| {
+            let mut chars = Vec::with_capacity(len);
+            for _ in 0..len {
+                chars.push(ALPHABET[idx % base]);
+                idx /= base;
+            }
+            chars.into_iter().rev().collect()
+        })
+        .collect()
+}
+
+impl LabelTexture {
+    fn get(
+        &mut self,
+        renderer: &mut GlesRenderer,
+        text: &str,
+        typed_len: usize,
+        scale: f64,
+    ) -> Option<TextureBuffer<GlesTexture>> {
+        if self.typed_len != typed_len || self.scale != scale {
+            self.texture = None;
+            self.typed_len = typed_len;
+            self.scale = scale;
+        }
+
+        self.texture
+            .get_or_insert_with(|| render_label(renderer, text, typed_len, scale).ok())
+            .clone()
+    }
+}
+
+fn render_label(
+    renderer: &mut GlesRenderer,
+    text: &str,
+    typed_len: usize,
+    scale: f64,
+) -> anyhow::Result<TextureBuffer<GlesTexture>> {
+    let _span = tracy_client::span!("window_labels::render_label");
+
+    let text = text.to_ascii_uppercase();
+    let (typed, rest) = text.split_at(typed_len);
+    let markup = format!("<span fgcolor='#777777'>{typed}</span>{rest}");
+
+    let mut font = FontDescription::from_string(FONT);
+    font.set_absolute_size(to_physical_precise_round(scale, font.size()));
+
+    let padding: i32 = to_physical_precise_round(scale, PADDING);
+
+    // Render to a dummy surface to determine the size.
+    let surface = ImageSurface::create(cairo::Format::ARgb32, 0, 0)?;
+    let cr = cairo::Context::new(&surface)?;
+    let layout = pangocairo::functions::create_layout(&cr);
+    layout.context().set_round_glyph_positions(false);
+    layout.set_font_description(Some(&font));
+    layout.set_markup(&markup);
+    let (mut width, mut height) = layout.pixel_size();
+
+    width += padding * 2;
+    height += padding * 2;
+
+    let surface = ImageSurface::create(cairo::Format::ARgb32, width, height)?;
+    let cr = cairo::Context::new(&surface)?;
+    cr.set_source_rgb(0.1, 0.1, 0.1);
+    cr.paint()?;
+
+    let padding = f64::from(padding);
+    cr.move_to(padding, padding);
+
+    let layout = pangocairo::functions::create_layout(&cr);
+    layout.context().set_round_glyph_positions(false);
+    layout.set_font_description(Some(&font));
+    layout.set_markup(&markup);
+
+    cr.set_source_rgb(1., 0.8, 0.3);
+    pangocairo::functions::show_layout(&cr, &layout);
+
+    cr.move_to(0., 0.);
+    cr.line_to(width.into(), 0.);
+    cr.line_to(width.into(), height.into());
+    cr.line_to(0., height.into());
+    cr.line_to(0., 0.);
+    cr.set_source_rgb(1., 0.8, 0.3);
+    // Keep the border width even to avoid blurry edges.
+    cr.set_line_width((f64::from(BORDER) / 2. * scale).round() * 2.);
+    cr.stroke()?;
+
+    drop(cr);
+    let data = surface.take_data().unwrap();
+    let buffer = TextureBuffer::from_memory(
+        renderer,
+        &data,
+        Fourcc::Argb8888,
+        (width, height),
+        false,
+        scale,
+        Transform::Normal,
+        Vec::new(),
+    )?;
+
+    Ok(buffer)
+}
+
+#[cfg(test)]
+mod tests {
+    use insta::assert_snapshot;
+
+    use super::*;
+
+    #[test]
+    fn test_generate_labels() {
+        assert_snapshot!(generate_labels(3).join(" "), @"a s d");
+        assert_snapshot!(generate_labels(9).join(" "), @"a s d f g h j k l");
+        assert_snapshot!(generate_labels(11).join(" "), @"aa as ad af ag ah aj ak al sa ss");
+
+        let labels = generate_labels(82);
+        assert_eq!(labels.len(), 82);
+        assert!(labels.iter().all(|label| label.len() == 3));
+    }
+
+    #[test]
+    fn test_type_char() {
+        let ids = [MappedId::next(), MappedId::next(), MappedId::next()];
+        let labels = ["aa", "as", "sa"]
+            .into_iter()
+            .zip(ids)
+            .map(|(text, id)| Label {
+                id,
+                text: String::from(text),
+                texture: Default::default(),
+            })
+            .collect();
+        let mut ui = WindowLabelUi {
+            state: State::Open {
+                labels,
+                typed: String::new(),
+            },
+        };
+
+        // Narrows down to the labels starting with "a".
+        assert_eq!(ui.type_char('a'), None);
+        assert!(ui.is_open());
+
+        // Doesn't continue any label, so it's ignored.
+        assert_eq!(ui.type_char('d'), None);
+        assert!(ui.is_open());
+
+        // Completes "as", case-insensitively.
+        assert_eq!(ui.type_char('S'), Some(ids[1]));
+        assert!(!ui.is_open());
+    }
+}
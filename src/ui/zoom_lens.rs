@@ -0,0 +1,179 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use anyhow::Context as _;
+use arrayvec::ArrayVec;
+use niri_config::Config;
+use smithay::backend::allocator::Fourcc;
+use smithay::backend::renderer::element::utils::{
+    Relocate, RelocateRenderElement, RescaleRenderElement,
+};
+use smithay::backend::renderer::element::{Kind, RenderElement};
+use smithay::backend::renderer::gles::{GlesRenderer, GlesTexture};
+use smithay::backend::renderer::{ContextId, Offscreen as _, Renderer as _, Texture as _};
+use smithay::utils::{Logical, Physical, Point, Scale, Size, Transform};
+
+use crate::niri_render_elements;
+use crate::render_helpers::primary_gpu_texture::PrimaryGpuTextureRenderElement;
+use crate::render_helpers::render_to_existing_texture;
+use crate::render_helpers::solid_color::{SolidColorBuffer, SolidColorRenderElement};
+use crate::render_helpers::texture::{TextureBuffer, TextureRenderElement};
+use crate::utils::round_logical_in_physical;
+
+const BORDER: f64 = 2.;
+const BORDER_COLOR: [f32; 4] = [0.5, 0.5, 0.5, 1.];
+
+/// Lens that magnifies the area under the cursor, leaving the rest of the screen as is.
+pub struct ZoomLens {
+    is_open: bool,
+    border: RefCell<SolidColorBuffer>,
+    /// Texture from the previous frame, reused while the lens size stays the same.
+    texture: RefCell<Option<LensTexture>>,
+    config: Rc<RefCell<Config>>,
+}
+
+struct LensTexture {
+    texture: GlesTexture,
+    /// Id of the renderer context that the texture comes from.
+    renderer_context_id: ContextId<GlesTexture>,
+}
+
+niri_render_elements! {
+    ZoomLensRenderElement => {
+        Texture = PrimaryGpuTextureRenderElement,
+        SolidColor = SolidColorRenderElement,
+    }
+}
+
+impl ZoomLens {
+    pub fn new(config: Rc<RefCell<Config>>) -> Self {
+        Self {
+            is_open: false,
+            border: RefCell::new(SolidColorBuffer::new((0., 0.), BORDER_COLOR)),
+            texture: RefCell::new(None),
+            config,
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.is_open
+    }
+
+    pub fn toggle(&mut self) {
+        self.is_open = !self.is_open;
+        self.texture.take();
+    }
+
+    /// Renders the lens centered on `pointer_pos`.
+    ///
+    /// `elements` are the output contents to magnify, front to back, in output-local coordinates.
+    pub fn render(
+        &self,
+        renderer: &mut GlesRenderer,
+        elements: &[impl RenderElement<GlesRenderer>],
+        pointer_pos: Point<f64, Logical>,
+        scale: f64,
+    ) -> ArrayVec<ZoomLensRenderElement, 2> {
+        let mut rv = ArrayVec::new();
+
+        if !self.is_open {
+            return rv;
+        }
+
+        let _span = tracy_client::span!("ZoomLens::render");
+
+        let config = self.config.borrow().zoom_lens;
+
+        let lens_size = Size::<f64, Logical>::from((config.width, config.height))
+            .to_physical_precise_round(scale)
+            .to_logical(scale);
+        let lens_loc = pointer_pos - lens_size.to_point().downscale(2.);
+        let lens_loc = lens_loc.to_physical_precise_round(scale).to_logical(scale);
+
+        // The area under the cursor that gets magnified to fill the lens.
+        let src_size = lens_size.downscale(config.magnification);
+        let src_loc = pointer_pos - src_size.to_point().downscale(2.);
+
+        // Scale the contents themselves up around the area rather than stretching a texture of
+        // it, so that the lens is drawn at its full resolution. The elements have their positions
+        // already in output physical pixels, so they can't be re-rendered at a different scale.
+        let origin: Point<i32, Physical> = src_loc.to_physical_precise_round(scale);
+        let elements = elements.iter().rev().map(|elem| {
+            let elem = RescaleRenderElement::from_element(elem, origin, config.magnification);
+            RelocateRenderElement::from_element(elem, origin.upscale(-1), Relocate::Relative)
+        });
+
+        let texture_size = lens_size.to_physical_precise_round(scale);
+        let texture = match self.render_texture(renderer, texture_size, scale, elements) {
+            Ok(texture) => texture,
+            Err(err) => {
+                warn!("error rendering zoom lens: {err:?}");
+                return rv;
+            }
+        };
+
+        let buffer =
+            TextureBuffer::from_texture(renderer, texture, scale, Transform::Normal, Vec::new());
+        let elem = TextureRenderElement::from_texture_buffer(
+            buffer,
+            lens_loc,
+            1.,
+            None,
+            Some(lens_size),
+            Kind::Unspecified,
+        );
+        rv.push(PrimaryGpuTextureRenderElement(elem).into());
+
+        let border = round_logical_in_physical(scale, BORDER);
+        let mut buffer = self.border.borrow_mut();
+        buffer.resize(lens_size + Size::from((border, border)).upscale(2.));
+        let elem = SolidColorRenderElement::from_buffer(
+            &buffer,
+            lens_loc - Point::from((border, border)),
+            1.,
+            Kind::Unspecified,
+        );
+        rv.push(elem.into());
+
+        rv
+    }
+
+    fn render_texture(
+        &self,
+        renderer: &mut GlesRenderer,
+        size: Size<i32, Physical>,
+        scale: f64,
+        elements: impl Iterator<Item = impl RenderElement<GlesRenderer>>,
+    ) -> anyhow::Result<GlesTexture> {
+        let buffer_size = size.to_logical(1).to_buffer(1, Transform::Normal);
+
+        let mut cached = self.texture.borrow_mut();
+        let reusable = cached.as_mut().is_some_and(|cached| {
+            cached.texture.size() == buffer_size
+                && cached.texture.is_unique_reference()
+                && cached.renderer_context_id == renderer.context_id()
+        });
+
+        let cached = if reusable {
+            cached.as_mut().unwrap()
+        } else {
+            let texture: GlesTexture = renderer
+                .create_buffer(Fourcc::Abgr8888, buffer_size)
+                .context("error creating texture")?;
+            cached.insert(LensTexture {
+                texture,
+                renderer_context_id: renderer.context_id(),
+            })
+        };
+
+        render_to_existing_texture(
+            renderer,
+            &mut cached.texture,
+            Scale::from(scale),
+            Transform::Normal,
+            elements,
+        )?;
+
+        Ok(cached.texture.clone())
+    }
+}
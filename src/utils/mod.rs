@@ -251,6 +251,20 @@ pub fn make_screenshot_path(config: &Config) -> anyhow::Result<Option<PathBuf>>
     Ok(Some(path))
 }
 
+/// Returns the current local time as hours, minutes and seconds.
+pub fn local_time() -> anyhow::Result<(i32, i32, i32)> {
+    unsafe {
+        let time = libc::time(null_mut());
+        ensure!(time != -1, "error in time()");
+
+        let tm = libc::localtime(&time);
+        ensure!(!tm.is_null(), "error in localtime()");
+
+        let tm = &*tm;
+        Ok((tm.tm_hour, tm.tm_min, tm.tm_sec))
+    }
+}
+
 pub fn write_png_rgba8(
     w: impl Write,
     width: u32,
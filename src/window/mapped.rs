@@ -1,4 +1,5 @@
 use std::cell::{Cell, Ref, RefCell};
+use std::cmp::Reverse;
 use std::time::Duration;
 
 use niri_config::{Color, CornerRadius, GradientInterpolation, WindowRule};
@@ -186,6 +187,11 @@ pub struct Mapped {
 
     /// Most recent monotonic time when the window had the focus.
     focus_timestamp: Option<Duration>,
+
+    /// Place in the recent windows order saved before a restart, lower is more recent.
+    ///
+    /// Only orders windows that haven't had the focus yet in this session.
+    restored_rank: Option<usize>,
 }
 
 niri_render_elements! {
@@ -283,6 +289,7 @@ impl Mapped {
             is_pending_maximized: false,
             uncommitted_maximized: Vec::new(),
             focus_timestamp: None,
+            restored_rank: None,
         };
 
         rv.is_maximized = rv.sizing_mode().is_maximized();
@@ -527,6 +534,17 @@ impl Mapped {
         self.focus_timestamp.replace(timestamp);
     }
 
+    pub fn set_restored_rank(&mut self, rank: usize) {
+        self.restored_rank = Some(rank);
+    }
+
+    /// Returns the key for sorting windows in the recent windows order, greater is more recent.
+    ///
+    /// Windows focused in this session come first, then the restored ones in their saved order.
+    pub fn mru_key(&self) -> (Option<Duration>, Option<Reverse<usize>>) {
+        (self.focus_timestamp, self.restored_rank.map(Reverse))
+    }
+
     pub fn send_frame<T, F>(
         &mut self,
         output: &Output,